@@ -1,7 +1,7 @@
 use clap::{Parser, Subcommand};
 use shuttle_common::{
     constants::API_URL_DEFAULT_BETA,
-    models::{project::ComputeTier, user::UserId},
+    models::{deployment::DeploymentState, project::ComputeTier, user::UserId},
 };
 
 #[derive(Parser, Debug)]
@@ -34,9 +34,55 @@ pub enum Command {
         compute_tier: ComputeTier,
     },
 
+    /// List the projects owned by an account
+    ListProjects {
+        /// Account to list projects for
+        #[arg(long, visible_alias = "user-id")]
+        account: UserId,
+    },
+
+    /// Search all projects, a page at a time
+    SearchProjects {
+        /// Only projects with a name or ID containing this
+        #[arg(long)]
+        search: Option<String>,
+        /// Only projects owned by this account
+        #[arg(long, visible_alias = "user-id")]
+        account: Option<UserId>,
+        /// Only projects with a current deployment in this state
+        #[arg(long)]
+        state: Option<DeploymentState>,
+        /// How many projects per page
+        #[arg(long, default_value_t = 100)]
+        limit: u32,
+        /// Cursor of the page to get, as printed after the previous page
+        #[arg(long)]
+        after: Option<String>,
+        /// Get all pages instead of one
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Delete a project and its resources, regardless of the state of its deployments
+    ForceDestroy {
+        /// Project to delete
+        #[arg(long, visible_alias = "id")]
+        project_id: String,
+        /// Skip the grace period before deleting
+        #[arg(long)]
+        yes: bool,
+    },
+
     /// Renew all custom domain certificates
     RenewCerts,
 
+    /// List custom domain certificates with their expiry and renewal status
+    ListCerts {
+        /// Only certificates that expire within this many days
+        #[arg(long)]
+        expiring_within_days: Option<u32>,
+    },
+
     /// Garbage collect free tier projects
     Gc {
         /// days since last deployment to filter by
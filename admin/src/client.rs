@@ -1,6 +1,14 @@
 use anyhow::Result;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use shuttle_api_client::ShuttleApiClient;
-use shuttle_common::models::project::{ComputeTier, ProjectResponse, ProjectUpdateRequest};
+use shuttle_common::models::{
+    certificate::AdminCertificateStatusListResponse,
+    deployment::DeploymentState,
+    project::{
+        AdminProjectSearchResponse, ComputeTier, ProjectListResponse, ProjectResponse,
+        ProjectUpdateRequest,
+    },
+};
 
 pub struct Client {
     pub inner: ShuttleApiClient,
@@ -19,6 +27,17 @@ impl Client {
             .await
     }
 
+    pub async fn get_certificate_statuses(
+        &self,
+        expiring_within_days: Option<u32>,
+    ) -> Result<AdminCertificateStatusListResponse> {
+        let path = match expiring_within_days {
+            Some(days) => format!("/admin/certificates?expiring_within_days={days}"),
+            None => "/admin/certificates".to_owned(),
+        };
+        self.inner.get_json(&path).await
+    }
+
     pub async fn update_project_compute_tier(
         &self,
         project_id: &str,
@@ -35,6 +54,42 @@ impl Client {
             .await
     }
 
+    pub async fn get_user_projects(&self, user_id: &str) -> Result<ProjectListResponse> {
+        let path = format!("/admin/users/{user_id}/projects");
+        self.inner.get_json(&path).await
+    }
+
+    pub async fn search_projects(
+        &self,
+        search: Option<&str>,
+        account: Option<&str>,
+        state: Option<&DeploymentState>,
+        limit: u32,
+        after: Option<&str>,
+    ) -> Result<AdminProjectSearchResponse> {
+        let mut path = format!("/admin/projects?limit={limit}");
+        let state = state.map(ToString::to_string);
+        for (key, value) in [
+            ("search", search),
+            ("account", account),
+            ("state", state.as_deref()),
+            ("after", after),
+        ] {
+            if let Some(value) = value {
+                path.push_str(&format!(
+                    "&{key}={}",
+                    utf8_percent_encode(value, NON_ALPHANUMERIC)
+                ));
+            }
+        }
+        self.inner.get_json(&path).await
+    }
+
+    pub async fn force_delete_project(&self, project_id: &str) -> Result<String> {
+        let path = format!("/admin/projects/{project_id}");
+        self.inner.delete_json(&path).await
+    }
+
     pub async fn gc_free_tier(&self, days: u32) -> Result<Vec<String>> {
         let path = format!("/admin/gc/free/{days}");
         self.inner.get_json(&path).await
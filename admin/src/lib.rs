@@ -20,10 +20,95 @@ pub async fn run(args: Args) {
         Command::ChangeProjectOwner { .. } => {
             unimplemented!();
         }
+        Command::ListProjects { account } => {
+            let res = client.get_user_projects(&account).await.unwrap();
+            for project in &res.projects {
+                println!("{}\t{}", project.id, project.name);
+            }
+            eprintln!("({} projects)", res.projects.len());
+        }
+        Command::SearchProjects {
+            search,
+            account,
+            state,
+            limit,
+            mut after,
+            all,
+        } => {
+            let mut count = 0;
+            loop {
+                let res = client
+                    .search_projects(
+                        search.as_deref(),
+                        account.as_deref(),
+                        state.as_ref(),
+                        limit,
+                        after.as_deref(),
+                    )
+                    .await
+                    .unwrap();
+                for project in &res.projects {
+                    let state = project
+                        .deployment_state
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default();
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        project.id, project.name, project.user_id, state
+                    );
+                }
+                count += res.projects.len();
+                after = res.next_cursor;
+                if !all || after.is_none() {
+                    break;
+                }
+            }
+            eprintln!("({count} projects)");
+            if let Some(cursor) = after {
+                eprintln!("Next page: --after {cursor}");
+            }
+        }
+        Command::ForceDestroy { project_id, yes } => {
+            if !yes {
+                let project = client.inner.get_project(&project_id).await.unwrap();
+                eprintln!(
+                    "Deleting project {} ({}) owned by {} in 5 seconds...",
+                    project.name, project.id, project.user_id
+                );
+                tokio::time::sleep(tokio::time::Duration::from_millis(5000)).await;
+            }
+            let res = client.force_delete_project(&project_id).await.unwrap();
+            println!("{res}");
+        }
         Command::RenewCerts => {
             let res = client.renew_old_certificates().await.unwrap();
             println!("{res}");
         }
+        Command::ListCerts {
+            expiring_within_days,
+        } => {
+            let res = client
+                .get_certificate_statuses(expiring_within_days)
+                .await
+                .unwrap();
+            for cert in &res.certificates {
+                let last_renewal = cert
+                    .last_renewal_at
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_else(|| "-".to_owned());
+                println!(
+                    "{}\t{}\t{}\t{}\tocsp={}\t{}",
+                    cert.project_id,
+                    cert.subject,
+                    cert.not_after,
+                    last_renewal,
+                    cert.ocsp_stapled,
+                    cert.last_renewal_error.as_deref().unwrap_or_default()
+                );
+            }
+            eprintln!("({} certificates)", res.certificates.len());
+        }
         Command::UpdateCompute {
             project_id,
             compute_tier,
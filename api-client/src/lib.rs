@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use headers::{Authorization, HeaderMapExt};
 use percent_encoding::utf8_percent_encode;
 use reqwest::header::HeaderMap;
@@ -9,17 +9,26 @@ use reqwest_middleware::{ClientWithMiddleware, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use shuttle_common::models::certificate::{
     AddCertificateRequest, CertificateListResponse, CertificateResponse, DeleteCertificateRequest,
+    DomainVerificationResponse, UpdateCertificateRequest, VerifyDomainRequest,
 };
 use shuttle_common::models::deployment::{
-    DeploymentListResponse, DeploymentRequest, DeploymentResponse, UploadArchiveResponse,
+    BuildCacheResponse, DeploymentListResponse, DeploymentPromoteRequest, DeploymentRequest,
+    DeploymentResponse, UploadArchiveResponse,
+};
+use shuttle_common::models::log::{
+    LogSearchRequest, LogSinkCreateRequest, LogSinkListResponse, LogSinkResponse, LogsResponse,
 };
-use shuttle_common::models::log::LogsResponse;
 use shuttle_common::models::project::{
-    ProjectCreateRequest, ProjectListResponse, ProjectResponse, ProjectUpdateRequest,
+    ProjectAccessRulesRequest, ProjectAccessRulesResponse, ProjectCreateRequest,
+    ProjectListResponse, ProjectResponse, ProjectSchedule, ProjectStatsResponse,
+    ProjectUpdateRequest, ProjectUsageResponse,
 };
 use shuttle_common::models::resource::{
-    ProvisionResourceRequest, ResourceListResponse, ResourceResponse, ResourceType,
+    DatabaseUsageListResponse, ProvisionResourceRequest, ResourceExportBundle,
+    ResourceHistoryResponse, ResourceListResponse, ResourceResponse, ResourceType,
+    SecretsUpdateRequest,
 };
+use shuttle_common::models::status::PlatformStatusResponse;
 use shuttle_common::models::{team, user};
 use tokio::net::TcpStream;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
@@ -97,6 +106,18 @@ impl ShuttleApiClient {
             .context("parsing name check response")
     }
 
+    /// Get the status of the platform. This is only informational, so it gives up quickly.
+    pub async fn get_platform_status(&self) -> Result<PlatformStatusResponse> {
+        self.client
+            .get(format!("{}/status", self.api_url))
+            .timeout(Duration::from_secs(3))
+            .send()
+            .await
+            .context("failed to get platform status")?
+            .to_json()
+            .await
+    }
+
     pub async fn get_current_user(&self) -> Result<user::UserResponse> {
         self.get_json("/users/me".to_owned()).await
     }
@@ -136,6 +157,51 @@ impl ShuttleApiClient {
         self.post_json(path, Option::<()>::None).await
     }
 
+    /// Deploy the build of a deployment to another project, returns the new deployment in that project
+    pub async fn promote_deployment(
+        &self,
+        project: &str,
+        deployment_id: &str,
+        target_project: &str,
+    ) -> Result<DeploymentResponse> {
+        let path = format!("/projects/{project}/deployments/{deployment_id}/promote");
+
+        self.post_json(
+            path,
+            Some(DeploymentPromoteRequest {
+                project_id: target_project.to_owned(),
+            }),
+        )
+        .await
+    }
+
+    pub async fn cancel_deployment(
+        &self,
+        project: &str,
+        deployment_id: &str,
+    ) -> Result<DeploymentResponse> {
+        let path = format!("/projects/{project}/deployments/{deployment_id}/cancel");
+
+        self.post_json(path, Option::<()>::None).await
+    }
+
+    pub async fn get_build_cache(&self, project: &str) -> Result<BuildCacheResponse> {
+        self.get_json(format!("/projects/{project}/build-cache"))
+            .await
+    }
+
+    /// Clear the target directory of the build cache, and the crate registry if `registry` is set
+    pub async fn clean_build_cache(
+        &self,
+        project: &str,
+        registry: bool,
+    ) -> Result<BuildCacheResponse> {
+        self.delete_json(format!(
+            "/projects/{project}/build-cache?registry={registry}"
+        ))
+        .await
+    }
+
     pub async fn stop_service(&self, project: &str) -> Result<String> {
         let path = format!("/projects/{project}/deployments");
 
@@ -147,27 +213,86 @@ impl ShuttleApiClient {
             .await
     }
 
-    async fn _dump_service_resource(
+    pub async fn get_service_resources_usage(
+        &self,
+        project: &str,
+    ) -> Result<DatabaseUsageListResponse> {
+        self.get_json(format!("/projects/{project}/resources/usage"))
+            .await
+    }
+
+    /// Get a dump of a database resource. The body is read in chunks, since dumps can be large.
+    /// `timeout` replaces the client's default timeout for this request.
+    pub async fn dump_service_resource(
         &self,
         project: &str,
         resource_type: &ResourceType,
-    ) -> Result<Vec<u8>> {
+        timeout: Duration,
+    ) -> Result<Response> {
         let r#type = resource_type.to_string();
         let r#type = utf8_percent_encode(&r#type, percent_encoding::NON_ALPHANUMERIC).to_owned();
 
-        let res = self
-            .get(
-                format!(
-                    "/projects/{project}/services/{project}/resources/{}/dump",
-                    r#type
-                ),
-                Option::<()>::None,
-            )
-            .await?;
+        let url = format!(
+            "{}/projects/{project}/resources/{}/dump",
+            self.api_url, r#type
+        );
+        let mut builder = self.client.get(url);
+        builder = self.set_auth_bearer(builder);
+
+        let res = builder
+            .timeout(timeout)
+            .send()
+            .await
+            .context("failed to request dump")?;
 
-        let bytes = res.bytes().await?;
+        if res.status() != http::StatusCode::OK {
+            // Turns the error in the body into an API error
+            res.to_json::<serde_json::Value>().await?;
+            bail!("failed to dump resource");
+        }
 
-        Ok(bytes.to_vec())
+        Ok(res)
+    }
+
+    /// Upload a dump to restore a database resource from. The file is streamed as the request body.
+    /// `timeout` replaces the client's default timeout for this request.
+    pub async fn restore_service_resource(
+        &self,
+        project: &str,
+        resource_type: &ResourceType,
+        dump: tokio::fs::File,
+        timeout: Duration,
+    ) -> Result<String> {
+        let r#type = resource_type.to_string();
+        let r#type = utf8_percent_encode(&r#type, percent_encoding::NON_ALPHANUMERIC).to_owned();
+
+        let url = format!(
+            "{}/projects/{project}/resources/{}/restore",
+            self.api_url, r#type
+        );
+        let mut builder = self.client.post(url);
+        builder = self.set_auth_bearer(builder);
+
+        builder
+            .body(reqwest::Body::from(dump))
+            .timeout(timeout)
+            .send()
+            .await
+            .context("failed to upload dump")?
+            .to_json()
+            .await
+    }
+
+    pub async fn get_service_resource_history(
+        &self,
+        project: &str,
+        resource_type: &ResourceType,
+    ) -> Result<ResourceHistoryResponse> {
+        let r#type = resource_type.to_string();
+        let r#type = utf8_percent_encode(&r#type, percent_encoding::NON_ALPHANUMERIC).to_owned();
+
+        self.get_json(format!("/projects/{project}/resources/{}/history", r#type))
+            .await
     }
 
     pub async fn delete_service_resource(
@@ -181,6 +306,39 @@ impl ShuttleApiClient {
         self.delete_json(format!("/projects/{project}/resources/{}", r#type))
             .await
     }
+
+    pub async fn rotate_service_resource_credentials(
+        &self,
+        project: &str,
+        resource_type: &ResourceType,
+    ) -> Result<ResourceResponse> {
+        let r#type = resource_type.to_string();
+        let r#type = utf8_percent_encode(&r#type, percent_encoding::NON_ALPHANUMERIC).to_owned();
+
+        self.post_json(
+            format!("/projects/{project}/resources/{}/rotate", r#type),
+            Option::<()>::None,
+        )
+        .await
+    }
+
+    pub async fn export_service_resources(&self, project: &str) -> Result<ResourceExportBundle> {
+        self.get_json(format!("/projects/{project}/resources/export"))
+            .await
+    }
+
+    pub async fn import_service_resources(
+        &self,
+        project: &str,
+        bundle: ResourceExportBundle,
+    ) -> Result<ResourceListResponse> {
+        self.post_json(
+            format!("/projects/{project}/resources/import"),
+            Some(bundle),
+        )
+        .await
+    }
+
     pub async fn provision_resource(
         &self,
         project: &str,
@@ -193,6 +351,14 @@ impl ShuttleApiClient {
         self.get_json(format!("/projects/{project}/resources/secrets"))
             .await
     }
+    pub async fn update_secrets(
+        &self,
+        project: &str,
+        req: SecretsUpdateRequest,
+    ) -> Result<ResourceResponse> {
+        self.put_json(format!("/projects/{project}/resources/secrets"), Some(req))
+            .await
+    }
 
     pub async fn list_certificates(&self, project: &str) -> Result<CertificateListResponse> {
         self.get_json(format!("/projects/{project}/certificates"))
@@ -209,6 +375,26 @@ impl ShuttleApiClient {
         )
         .await
     }
+    /// Check the DNS records of a custom domain, before adding a certificate for it
+    pub async fn verify_domain(
+        &self,
+        project: &str,
+        subject: String,
+    ) -> Result<DomainVerificationResponse> {
+        self.post_json(
+            format!("/projects/{project}/certificates/verify"),
+            Some(VerifyDomainRequest { subject }),
+        )
+        .await
+    }
+    pub async fn update_certificate(
+        &self,
+        project: &str,
+        req: UpdateCertificateRequest,
+    ) -> Result<CertificateResponse> {
+        self.put_json(format!("/projects/{project}/certificates"), Some(req))
+            .await
+    }
     pub async fn delete_certificate(&self, project: &str, subject: String) -> Result<String> {
         self.delete_json_with_body(
             format!("/projects/{project}/certificates"),
@@ -231,6 +417,51 @@ impl ShuttleApiClient {
         self.get_json(format!("/projects/{project}")).await
     }
 
+    pub async fn get_project_stats(&self, project: &str) -> Result<ProjectStatsResponse> {
+        self.get_json(format!("/projects/{project}/stats")).await
+    }
+
+    pub async fn get_project_usage(&self, project: &str) -> Result<ProjectUsageResponse> {
+        self.get_json(format!("/projects/{project}/usage")).await
+    }
+
+    /// Stream of [`shuttle_common::models::project::ProjectEvent`]s as JSON text messages
+    pub async fn get_project_events_ws(
+        &self,
+        project: &str,
+    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+        self.ws_get(format!("/projects/{project}/events")).await
+    }
+
+    pub async fn get_project_access_rules(
+        &self,
+        project: &str,
+    ) -> Result<ProjectAccessRulesResponse> {
+        self.get_json(format!("/projects/{project}/access")).await
+    }
+
+    pub async fn set_project_access_rules(
+        &self,
+        project: &str,
+        req: ProjectAccessRulesRequest,
+    ) -> Result<ProjectAccessRulesResponse> {
+        self.put_json(format!("/projects/{project}/access"), Some(req))
+            .await
+    }
+
+    pub async fn get_project_schedule(&self, project: &str) -> Result<ProjectSchedule> {
+        self.get_json(format!("/projects/{project}/schedule")).await
+    }
+
+    pub async fn set_project_schedule(
+        &self,
+        project: &str,
+        schedule: ProjectSchedule,
+    ) -> Result<ProjectSchedule> {
+        self.put_json(format!("/projects/{project}/schedule"), Some(schedule))
+            .await
+    }
+
     pub async fn get_projects_list(&self) -> Result<ProjectListResponse> {
         self.get_json("/projects".to_owned()).await
     }
@@ -264,11 +495,42 @@ impl ShuttleApiClient {
 
         self.get_json(path).await
     }
+    pub async fn get_deployment_build_logs(
+        &self,
+        project: &str,
+        deployment_id: &str,
+    ) -> Result<LogsResponse> {
+        let path = format!("/projects/{project}/deployments/{deployment_id}/logs/build");
+
+        self.get_json(path).await
+    }
     pub async fn get_project_logs(&self, project: &str) -> Result<LogsResponse> {
         let path = format!("/projects/{project}/logs");
 
         self.get_json(path).await
     }
+    pub async fn search_logs(&self, project: &str, req: &LogSearchRequest) -> Result<LogsResponse> {
+        let path = format!("/projects/{project}/logs/search");
+
+        self.post_json(path, Some(req)).await
+    }
+
+    pub async fn get_log_sinks(&self, project: &str) -> Result<LogSinkListResponse> {
+        self.get_json(format!("/projects/{project}/logs/sinks"))
+            .await
+    }
+    pub async fn add_log_sink(
+        &self,
+        project: &str,
+        req: LogSinkCreateRequest,
+    ) -> Result<LogSinkResponse> {
+        self.post_json(format!("/projects/{project}/logs/sinks"), Some(req))
+            .await
+    }
+    pub async fn delete_log_sink(&self, project: &str, sink_id: &str) -> Result<String> {
+        self.delete_json(format!("/projects/{project}/logs/sinks/{sink_id}"))
+            .await
+    }
 
     pub async fn get_deployments(
         &self,
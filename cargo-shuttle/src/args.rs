@@ -2,11 +2,13 @@ use std::{
     ffi::OsString,
     fs::create_dir_all,
     io::{self, ErrorKind},
+    net::IpAddr,
     path::PathBuf,
 };
 
 use anyhow::{bail, Context};
 use cargo_metadata::MetadataCommand;
+use chrono::{DateTime, Utc};
 use clap::{
     builder::{OsStringValueParser, PossibleValue, TypedValueParser},
     Args, Parser, Subcommand, ValueEnum,
@@ -14,7 +16,11 @@ use clap::{
 use clap_complete::Shell;
 use shuttle_common::{
     constants::{EXAMPLES_REPO, SHUTTLE_CONSOLE_URL},
-    models::resource::ResourceType,
+    models::{
+        deployment::DeploymentState,
+        log::{LogSinkKind, LogTimestamps},
+        resource::ResourceType,
+    },
 };
 
 #[derive(Parser)]
@@ -32,6 +38,9 @@ pub struct ShuttleArgs {
     /// URL for the Shuttle API to target (mainly for development)
     #[arg(global = true, long, env = "SHUTTLE_API", hide = true)]
     pub api_url: Option<String>,
+    /// Use the API URL, API key and project of this profile in the global config
+    #[arg(global = true, long, env = "SHUTTLE_PROFILE")]
+    pub profile: Option<String>,
     /// Disable network requests that are not strictly necessary. Limits some features.
     #[arg(global = true, long, env = "SHUTTLE_OFFLINE")]
     pub offline: bool,
@@ -115,8 +124,11 @@ pub enum Command {
     #[command(subcommand, visible_alias = "res")]
     Resource(ResourceCommand),
     /// Manage SSL certificates for custom domains
-    #[command(subcommand, visible_alias = "cert")]
+    #[command(subcommand, visible_aliases = ["cert", "domain"])]
     Certificate(CertificateCommand),
+    /// Manage secrets without making a new deployment
+    #[command(subcommand)]
+    Secrets(SecretsCommand),
     /// Show info about your Shuttle account
     #[command(visible_alias = "acc")]
     Account,
@@ -127,9 +139,25 @@ pub enum Command {
     /// Generate shell completions and man page
     #[command(subcommand)]
     Generate(GenerateCommand),
+    /// Clear the build cache of the project on Shuttle, which makes the next build start from scratch
+    Clean {
+        /// Also clear the downloaded crates
+        #[arg(long)]
+        deep: bool,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
     /// Open an issue on GitHub and provide feedback
-    Feedback,
-    /// Upgrade the Shuttle CLI binary
+    Feedback {
+        /// Fill in the issue with the latest crash report
+        #[arg(long)]
+        attach_report: bool,
+    },
+    /// Manage opt-in crash reports
+    #[command(subcommand)]
+    Telemetry(TelemetryCommand),
+    /// Upgrade the Shuttle CLI binary to the latest release.
+    /// Uses cargo-binstall or the install script if available, and cargo install otherwise.
     Upgrade {
         /// Install an unreleased version from the repository's main branch
         #[arg(long)]
@@ -137,6 +165,17 @@ pub enum Command {
     },
 }
 
+#[derive(Subcommand)]
+pub enum TelemetryCommand {
+    /// Show whether crash reports are enabled
+    Status,
+    /// Save a local crash report when the CLI panics or a deployment fails.
+    /// Reports are never sent anywhere, see `feedback --attach-report`.
+    Enable,
+    /// Stop saving crash reports
+    Disable,
+}
+
 #[derive(Subcommand)]
 pub enum GenerateCommand {
     /// Generate shell completions
@@ -151,7 +190,7 @@ pub enum GenerateCommand {
     Manpage,
 }
 
-#[derive(Args)]
+#[derive(Args, Clone, Debug)]
 #[command(next_help_heading = "Table options")]
 pub struct TableArgs {
     /// Output tables without borders
@@ -181,11 +220,39 @@ pub enum DeploymentCommand {
         /// ID of deployment to get status for
         id: Option<String>,
     },
+    /// Follow the status of a deployment until it is running or has failed
+    Follow {
+        /// ID of deployment to follow [default: the latest deployment]
+        id: Option<String>,
+        /// Don't display timestamps and log origin tags in the logs of a failed deployment
+        #[arg(long)]
+        raw: bool,
+    },
     /// Redeploy a previous deployment (if possible)
     Redeploy {
         /// ID of deployment to redeploy
         id: Option<String>,
     },
+    /// Deploy the build of a deployment to another project, e.g. from staging to production
+    Promote {
+        /// ID of deployment to promote [default: the latest deployment]
+        id: Option<String>,
+        /// Name or ID of the project to deploy to
+        #[arg(long)]
+        to: String,
+    },
+    /// Show what changed between two deployments
+    Diff {
+        /// ID of the deployment to compare from
+        from: String,
+        /// ID of the deployment to compare to [default: the latest deployment]
+        to: Option<String>,
+    },
+    /// Cancel a deployment that is queued or building
+    Cancel {
+        /// ID of deployment to cancel [default: the latest deployment]
+        id: Option<String>,
+    },
     /// Stop running deployment(s)
     Stop,
 }
@@ -201,6 +268,8 @@ pub enum ResourceCommand {
 
         #[command(flatten)]
         table: TableArgs,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
     },
     /// Delete a resource
     #[command(visible_alias = "rm")]
@@ -212,13 +281,64 @@ pub enum ResourceCommand {
         #[command(flatten)]
         confirmation: ConfirmationArgs,
     },
-    /// Dump a resource
-    #[command(hide = true)] // not yet supported on shuttle.dev
+    /// Change the password of a database resource.
+    /// The service is redeployed to pick up the new connection info.
+    Rotate {
+        /// Type of the resource to rotate the credentials of.
+        /// Use the string in the 'Type' column as displayed in the `resource list` command.
+        /// For example, 'database::shared::postgres'.
+        resource_type: ResourceType,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
+    /// Show when a resource was created, updated, or deleted, and by whom
+    History {
+        /// Type of the resource.
+        /// Use the string in the 'Type' column as displayed in the `resource list` command.
+        /// For example, 'database::shared::postgres'.
+        resource_type: ResourceType,
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    /// Export all resources of a project to a file, for importing them into another project
+    Export {
+        /// File to write the export to. It contains secrets, so it is never printed.
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// Import resources from a file created with `resource export`
+    Import {
+        /// Path to the exported resources
+        file: PathBuf,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
+    /// Download a dump of a database, such as a pg_dump of a shared Postgres database
     Dump {
         /// Type of the resource to dump.
         /// Use the string in the 'Type' column as displayed in the `resource list` command.
         /// For example, 'database::shared::postgres'.
         resource_type: ResourceType,
+        /// File to write the dump to. Prints to stdout if not set.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Seconds to wait for the dump to finish downloading
+        #[arg(long, default_value_t = 3600)]
+        timeout: u64,
+    },
+    /// Restore a database from a dump created with `resource dump`
+    Restore {
+        /// Type of the resource to restore.
+        /// Use the string in the 'Type' column as displayed in the `resource list` command.
+        /// For example, 'database::shared::postgres'.
+        resource_type: ResourceType,
+        /// Path to the dump
+        file: PathBuf,
+        /// Seconds to wait for the upload and restore to finish
+        #[arg(long, default_value_t = 3600)]
+        timeout: u64,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
     },
 }
 
@@ -229,12 +349,34 @@ pub enum CertificateCommand {
         /// Domain name
         domain: String,
     },
+    /// Check that the DNS records of a custom domain are set up for a certificate
+    Verify {
+        /// Domain name
+        domain: String,
+        /// Keep checking until the records are correct
+        #[arg(long)]
+        wait: bool,
+    },
     /// List the certificates for a project
     #[command(visible_alias = "ls")]
     List {
         #[command(flatten)]
         table: TableArgs,
     },
+    /// Change how HTTP requests to a custom domain are handled
+    Update {
+        /// Domain name
+        domain: String,
+        /// Send the Strict-Transport-Security header on HTTPS responses
+        #[arg(long)]
+        hsts: Option<bool>,
+        /// Serve these path prefixes over plain HTTP instead of redirecting to HTTPS, e.g. for webhook verification (comma-separated)
+        #[arg(long, value_delimiter = ',', conflicts_with = "no_http_paths")]
+        http_path: Option<Vec<String>>,
+        /// Redirect all plain HTTP requests to HTTPS again
+        #[arg(long)]
+        no_http_paths: bool,
+    },
     /// Delete an SSL certificate
     #[command(visible_alias = "rm")]
     Delete {
@@ -245,6 +387,37 @@ pub enum CertificateCommand {
     },
 }
 
+#[derive(Subcommand)]
+pub enum SecretsCommand {
+    /// List the keys of the secrets for a project
+    #[command(visible_alias = "ls")]
+    List {
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    /// Add a secret, or overwrite its value if it already exists
+    Set {
+        /// Key of the secret
+        key: String,
+        /// Value of the secret
+        value: String,
+    },
+    /// Remove a secret
+    #[command(visible_alias = "rm")]
+    Unset {
+        /// Key of the secret
+        key: String,
+    },
+    /// Download the secrets of a project into a secrets file
+    Pull {
+        /// File to write the secrets to [default: Secrets.toml in the project directory]
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ProjectCommand {
     /// Create a project on Shuttle
@@ -255,10 +428,55 @@ pub enum ProjectCommand {
     Update(ProjectUpdateCommand),
     /// Get the status of this project on Shuttle
     #[command(visible_alias = "stat")]
-    Status,
+    Status {
+        /// Keep printing the deployment state changes of the project as they happen
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Show request counts and latency for this project
+    Stats,
+    /// Show the requests and egress of this project this month, and the limits of its plan
+    Usage,
+    /// Rename the project, same as `project update name`
+    Rename {
+        /// New project name
+        name: String,
+    },
+    /// Restrict who can reach this project's subdomains. Shows the current rules if no option is given.
+    Protect {
+        /// Only allow requests from these IP ranges, e.g. `203.0.113.0/24` (comma-separated)
+        #[arg(long, value_delimiter = ',', value_parser = parse_ip_range)]
+        allow_ip: Vec<String>,
+        /// Require HTTP basic auth for this user. The password is prompted for.
+        #[arg(long, value_name = "USER")]
+        basic_auth: Option<String>,
+        /// Remove all rules and make the project public again
+        #[arg(long, conflicts_with_all = ["allow_ip", "basic_auth"])]
+        remove: bool,
+    },
+    /// Stop and start this project at set times, e.g. to save idle time overnight.
+    /// Shows the current schedule if no option is given.
+    Schedule {
+        /// When to stop the project, as a cron expression in UTC, e.g. "0 22 * * *"
+        #[arg(long, value_name = "CRON", value_parser = parse_cron)]
+        stop: Option<String>,
+        /// When to start the project again, as a cron expression in UTC, e.g. "0 7 * * 1-5"
+        #[arg(long, value_name = "CRON", value_parser = parse_cron)]
+        start: Option<String>,
+        /// Remove the schedule
+        #[arg(long, conflicts_with_all = ["stop", "start"])]
+        remove: bool,
+    },
     /// List all projects you have access to
     #[command(visible_alias = "ls")]
     List {
+        /// Only show projects where the current deployment is in one of these states (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        state: Vec<DeploymentState>,
+        /// Sort the projects by this field
+        #[arg(long, value_enum)]
+        sort: Option<ProjectListSort>,
+
         #[command(flatten)]
         table: TableArgs,
     },
@@ -269,6 +487,14 @@ pub enum ProjectCommand {
     Link,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ProjectListSort {
+    /// Alphabetically by project name
+    Name,
+    /// Newest projects first
+    Created,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ProjectUpdateCommand {
     /// Rename the project, including its default subdomain
@@ -291,6 +517,9 @@ pub struct LoginArgs {
     /// Log in with this Shuttle API key
     #[arg(long)]
     pub api_key: Option<String>,
+    /// Don't open a browser, and show the login URL to open on another device instead
+    #[arg(long, conflicts_with_all = ["api_key", "prompt"])]
+    pub device: bool,
     /// URL to the Shuttle Console for automatic login
     #[arg(long, env = "SHUTTLE_CONSOLE", default_value = SHUTTLE_CONSOLE_URL, hide_default_value = true)]
     pub console_url: String,
@@ -305,22 +534,57 @@ pub struct LogoutArgs {
 
 #[derive(Args, Default)]
 pub struct DeployArgs {
-    /// WIP: Deploy this Docker image instead of building one
-    #[arg(long, short = 'i', hide = true)]
+    /// Deploy this prebuilt image instead of building one, optionally pinned by digest (image@sha256:...)
+    #[arg(long, short = 'i', value_parser = parse_image)]
     pub image: Option<String>,
+    /// Name of the workspace member to deploy, if the workspace has several Shuttle services
+    #[arg(long)]
+    pub package: Option<String>,
+    /// Deploy all Shuttle services in the workspace at the same time.
+    /// Each service is deployed to the project with the name of its package, which is created if needed.
+    /// A service uses the Secrets.toml in its package directory if there is one, otherwise the workspace's.
+    #[arg(long, conflicts_with_all = ["package", "image", "output_archive", "dry_run"])]
+    pub all: bool,
     /// Don't follow the deployment status, exit after the deployment begins
-    #[arg(long, visible_alias = "nf")]
+    #[arg(long, visible_aliases = ["nf", "no-wait"])]
     pub no_follow: bool,
+    /// Create missing projects without asking, when deploying with `--all`
+    #[arg(long, short = 'y', requires = "all")]
+    pub yes: bool,
 
     /// Allow deployment with uncommitted files
     #[arg(long, visible_alias = "ad")]
     pub allow_dirty: bool,
+    /// Do a clean build instead of reusing the build cache from previous deployments
+    #[arg(long)]
+    pub no_cache: bool,
     /// Don't display timestamps and log origin tags
     #[arg(long)]
     pub raw: bool,
     /// Output the deployment archive to a file instead of sending a deployment request
     #[arg(long)]
     pub output_archive: Option<PathBuf>,
+    /// Merge the secrets in Secrets.<ENV>.toml over the ones in Secrets.toml, e.g. `--secrets-env staging`
+    #[arg(long, value_name = "ENV")]
+    pub secrets_env: Option<String>,
+    /// List the files that would be packed into the deployment archive, with their sizes, without deploying
+    #[arg(long, conflicts_with = "image")]
+    pub dry_run: bool,
+    /// Set an environment variable for this deployment, e.g. `--env RUST_LOG=debug`. Use Secrets.toml for secret values.
+    #[arg(long, short = 'e', value_name = "KEY=VALUE", value_parser = parse_define)]
+    pub env: Vec<(String, String)>,
+    /// Token for fetching private git dependencies from a host during the build, e.g. `github.com=<token>`.
+    /// Set it with the environment variable to keep the token out of the shell history.
+    #[arg(
+        long,
+        value_name = "HOST=TOKEN",
+        value_parser = parse_define,
+        env = "SHUTTLE_GIT_CREDENTIALS",
+        value_delimiter = ',',
+        hide_env_values = true,
+        conflicts_with = "image"
+    )]
+    pub git_credential: Vec<(String, String)>,
 
     #[command(flatten)]
     pub secret_args: SecretsArgs,
@@ -340,6 +604,24 @@ pub struct RunArgs {
     /// Don't display timestamps and log origin tags
     #[arg(long)]
     pub raw: bool,
+    /// Watch the project for changes, then rebuild and restart the service
+    #[arg(long, short = 'w')]
+    pub watch: bool,
+    /// Run a workspace member on a fixed port, e.g. `--port-map api=8005,web=8006`.
+    /// Other services get ports counting up from `--port`.
+    #[arg(long, value_delimiter = ',', value_parser = parse_port_mapping)]
+    pub port_map: Vec<(String, u16)>,
+    /// Build and run the services in a Debian container like on Shuttle, to reproduce
+    /// issues caused by differences in system libraries. Uses the host network, so only works on Linux.
+    #[arg(long)]
+    pub docker: bool,
+    /// SQL file to run in local databases of an engine the first time they start,
+    /// e.g. `--seed postgres=seed.sql`. Engines: postgres, mysql, mariadb.
+    #[arg(long, value_name = "ENGINE=FILE", value_parser = parse_seed)]
+    pub seed: Vec<(String, PathBuf)>,
+    /// Run the seed files again in local databases that were already seeded
+    #[arg(long, requires = "seed")]
+    pub reseed: bool,
 
     #[command(flatten)]
     pub secret_args: SecretsArgs,
@@ -363,6 +645,12 @@ pub struct InitArgs {
     /// Path to the template in the source (used with --from)
     #[arg(long, requires = "from")]
     pub subfolder: Option<String>,
+    /// Set a placeholder value of a cargo-generate template (can be used multiple times)
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_define)]
+    pub define: Vec<(String, String)>,
+    /// Adapt the existing Axum, Actix Web or Rocket crate at the path to run on Shuttle
+    #[arg(long, conflicts_with_all = &["template", "from", "subfolder"])]
+    pub from_cargo: bool,
 
     /// Path where to place the new Shuttle project
     #[arg(default_value = ".", value_parser = OsStringValueParser::new().try_map(create_and_parse_path))]
@@ -461,7 +749,10 @@ impl InitTemplateArg {
 }
 
 #[derive(Args, Clone, Debug, Default)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct LogsArgs {
+    #[command(subcommand)]
+    pub cmd: Option<LogsCommand>,
     /// Deployment ID to get logs for. Defaults to the current deployment
     pub id: Option<String>,
     #[arg(short, long)]
@@ -473,11 +764,17 @@ pub struct LogsArgs {
     /// Don't display timestamps and log origin tags
     #[arg(long)]
     pub raw: bool,
+    /// Show timestamps in local time, in UTC, or not at all [possible values: local, utc, off]
+    #[arg(long, default_value = "local")]
+    pub timestamps: LogTimestamps,
+    /// Don't color the output, and strip colors from the log lines
+    #[arg(long)]
+    pub no_color: bool,
     /// View the first N log lines
-    #[arg(long, group = "output_mode", hide = true)]
+    #[arg(long, group = "output_mode")]
     pub head: Option<u32>,
     /// View the last N log lines
-    #[arg(long, group = "output_mode", hide = true)]
+    #[arg(long, group = "output_mode")]
     pub tail: Option<u32>,
     /// View all log lines
     #[arg(long, group = "output_mode", hide = true)]
@@ -485,6 +782,67 @@ pub struct LogsArgs {
     /// Get logs from all deployments instead of one deployment
     #[arg(long)]
     pub all_deployments: bool,
+    /// Only get the build output of the deployment
+    #[arg(long, conflicts_with = "all_deployments")]
+    pub build: bool,
+    /// Only get log lines containing this text. The search is done by Shuttle, so only matching lines are downloaded
+    #[arg(long, value_name = "PATTERN", conflicts_with = "build")]
+    pub grep: Option<String>,
+    /// Use the `--grep` pattern as a regular expression
+    #[arg(long, requires = "grep")]
+    pub regex: bool,
+    /// Only search log lines from this time on, e.g. `2025-01-31T12:00:00Z`
+    #[arg(long, requires = "grep")]
+    pub since: Option<DateTime<Utc>>,
+    /// Only search log lines up to this time, e.g. `2025-01-31T13:00:00Z`
+    #[arg(long, requires = "grep")]
+    pub until: Option<DateTime<Utc>>,
+    /// Output format of the log lines
+    #[arg(long, value_enum, default_value_t = LogsOutput::Text)]
+    pub output: LogsOutput,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum LogsCommand {
+    /// Manage external services that the logs of this project are forwarded to
+    #[command(subcommand)]
+    Sink(LogSinkCommand),
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum LogSinkCommand {
+    /// Forward logs to an external service
+    Add {
+        /// Kind of service [possible values: loki, syslog, webhook]
+        #[arg(long, default_value = "webhook")]
+        kind: LogSinkKind,
+        /// URL of the service, e.g. `https://loki.example.com/loki/api/v1/push`
+        url: String,
+        /// Header to send with each batch, e.g. `--header Authorization="Bearer ..."`
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_define)]
+        header: Vec<(String, String)>,
+    },
+    /// List the log sinks of this project
+    #[command(visible_alias = "ls")]
+    List {
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    /// Stop forwarding logs to a service
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// ID of the sink, as shown by `logs sink list`
+        id: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogsOutput {
+    /// Formatted log lines
+    #[default]
+    Text,
+    /// One JSON object per line, including the structured fields of log events
+    Json,
 }
 
 /// Helper function to parse and return the absolute path
@@ -497,6 +855,145 @@ fn parse_path(path: OsString) -> Result<PathBuf, io::Error> {
     })
 }
 
+/// Helper function to parse a `service=port` mapping
+fn parse_port_mapping(mapping: &str) -> Result<(String, u16), String> {
+    let (service, port) = mapping
+        .split_once('=')
+        .ok_or_else(|| format!("invalid port mapping '{mapping}', expected <service>=<port>"))?;
+    let port = port
+        .parse()
+        .map_err(|e| format!("invalid port in mapping '{mapping}': {e}"))?;
+
+    Ok((service.to_owned(), port))
+}
+
+/// Helper function to parse a seed file for a database engine, given as `engine=path`
+fn parse_seed(seed: &str) -> Result<(String, PathBuf), String> {
+    let (engine, path) = seed
+        .split_once('=')
+        .ok_or_else(|| format!("invalid seed '{seed}', expected <engine>=<file>"))?;
+    if !["postgres", "mysql", "mariadb"].contains(&engine) {
+        return Err(format!(
+            "unknown database engine '{engine}', expected postgres, mysql or mariadb"
+        ));
+    }
+    let path = parse_path(OsString::from(path)).map_err(|e| e.to_string())?;
+
+    Ok((engine.to_owned(), path))
+}
+
+/// Helper function to parse a `key=value` pair, such as a template placeholder value
+fn parse_define(define: &str) -> Result<(String, String), String> {
+    let (key, value) = define
+        .split_once('=')
+        .ok_or_else(|| format!("invalid value '{define}', expected <key>=<value>"))?;
+    if key.is_empty() {
+        return Err(format!("missing key in '{define}'"));
+    }
+
+    Ok((key.to_owned(), value.to_owned()))
+}
+
+/// Helper function to check that an IP range is an IP address with an optional CIDR prefix length
+fn parse_ip_range(range: &str) -> Result<String, String> {
+    let (ip, prefix) = match range.split_once('/') {
+        Some((ip, prefix)) => (ip, Some(prefix)),
+        None => (range, None),
+    };
+    let ip: IpAddr = ip
+        .parse()
+        .map_err(|e| format!("invalid IP address in '{range}': {e}"))?;
+    if let Some(prefix) = prefix {
+        let max = if ip.is_ipv4() { 32 } else { 128 };
+        match prefix.parse::<u8>() {
+            Ok(p) if p <= max => {}
+            _ => {
+                return Err(format!(
+                    "invalid prefix length in '{range}', expected 0 to {max}"
+                ))
+            }
+        }
+    }
+
+    Ok(range.to_owned())
+}
+
+/// Helper function to check that a cron expression has five fields of numbers, ranges, lists and steps
+fn parse_cron(cron: &str) -> Result<String, String> {
+    // Names and allowed values of the fields. Both 0 and 7 are Sunday.
+    const FIELDS: [(&str, u32, u32); 5] = [
+        ("minute", 0, 59),
+        ("hour", 0, 23),
+        ("day", 1, 31),
+        ("month", 1, 12),
+        ("weekday", 0, 7),
+    ];
+
+    let fields = cron.split_whitespace().collect::<Vec<_>>();
+    if fields.len() != FIELDS.len() {
+        return Err(format!(
+            "invalid cron expression '{cron}', expected five fields: minute hour day month weekday"
+        ));
+    }
+    for (field, (name, min, max)) in fields.iter().zip(FIELDS) {
+        if !field
+            .split(',')
+            .all(|item| is_valid_cron_item(item, min, max))
+        {
+            return Err(format!(
+                "invalid {name} '{field}' in cron expression '{cron}', expected values from {min} to {max}"
+            ));
+        }
+    }
+
+    Ok(fields.join(" "))
+}
+
+/// One item of a cron field list: `*`, a number or a range, optionally followed by `/step`
+fn is_valid_cron_item(item: &str, min: u32, max: u32) -> bool {
+    let (range, step) = match item.split_once('/') {
+        Some((range, step)) => (range, Some(step)),
+        None => (item, None),
+    };
+    if let Some(step) = step {
+        if !step
+            .parse::<u32>()
+            .is_ok_and(|step| step >= 1 && step <= max)
+        {
+            return false;
+        }
+    }
+    if range == "*" {
+        return true;
+    }
+    let value = |v: &str| v.parse::<u32>().ok().filter(|v| (min..=max).contains(v));
+    match range.split_once('-') {
+        Some((start, end)) => {
+            matches!((value(start), value(end)), (Some(start), Some(end)) if start <= end)
+        }
+        None => value(range).is_some(),
+    }
+}
+
+/// Helper function to check that an image reference with a digest has a valid sha256 digest
+fn parse_image(image: &str) -> Result<String, String> {
+    if image.is_empty() || image.contains(char::is_whitespace) {
+        return Err("invalid image reference".to_owned());
+    }
+    if let Some((_, digest)) = image.split_once('@') {
+        let valid = digest.strip_prefix("sha256:").is_some_and(|hex| {
+            hex.len() == 64 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+        });
+        if !valid {
+            return Err(format!(
+                "invalid image digest '{digest}', expected sha256:<64 hex characters>"
+            ));
+        }
+    }
+
+    Ok(image.to_owned())
+}
+
 /// Helper function to parse, create if not exists, and return the absolute path
 pub(crate) fn create_and_parse_path(path: OsString) -> Result<PathBuf, io::Error> {
     // Create the directory if does not exist
@@ -593,6 +1090,61 @@ mod tests {
         assert_eq!(init_args.git_template().unwrap(), None);
     }
 
+    #[test]
+    fn port_mapping() {
+        assert_eq!(parse_port_mapping("api=8005"), Ok(("api".to_owned(), 8005)));
+        assert!(parse_port_mapping("api").is_err());
+        assert!(parse_port_mapping("api=http").is_err());
+        assert!(parse_port_mapping("api=70000").is_err());
+    }
+
+    #[test]
+    fn seed() {
+        let (engine, path) = parse_seed("postgres=Cargo.toml").unwrap();
+        assert_eq!(engine, "postgres");
+        assert!(path.ends_with("Cargo.toml"));
+        assert!(parse_seed("Cargo.toml").is_err());
+        assert!(parse_seed("redis=Cargo.toml").is_err());
+        assert!(parse_seed("postgres=does-not-exist.sql").is_err());
+    }
+
+    #[test]
+    fn ip_range() {
+        assert!(parse_ip_range("203.0.113.7").is_ok());
+        assert!(parse_ip_range("203.0.113.0/24").is_ok());
+        assert!(parse_ip_range("2001:db8::/32").is_ok());
+        assert!(parse_ip_range("203.0.113.0/33").is_err());
+        assert!(parse_ip_range("example.com").is_err());
+    }
+
+    #[test]
+    fn cron() {
+        assert_eq!(parse_cron("0  22 * * *").unwrap(), "0 22 * * *");
+        assert!(parse_cron("*/15 7-9 1,15 * 1-5").is_ok());
+        assert!(parse_cron("0 22 * *").is_err());
+        assert!(parse_cron("0 22 * * MON").is_err());
+        assert!(parse_cron("0,30 0-23/2 */10 1-12 0,7").is_ok());
+        assert!(parse_cron("99 99 * * *").is_err());
+        assert!(parse_cron("-,- * * * *").is_err());
+        assert!(parse_cron("0 22 0 * *").is_err());
+        assert!(parse_cron("0 22 * 13 *").is_err());
+        assert!(parse_cron("0 22 * * 8").is_err());
+        assert!(parse_cron("0 9-5 * * *").is_err());
+        assert!(parse_cron("*/0 * * * *").is_err());
+        assert!(parse_cron("0/ * * * *").is_err());
+        assert!(parse_cron("0,,5 * * * *").is_err());
+    }
+
+    #[test]
+    fn image_digest() {
+        let digest = "a".repeat(64);
+        assert!(parse_image("ghcr.io/org/app:v1").is_ok());
+        assert!(parse_image(&format!("ghcr.io/org/app@sha256:{digest}")).is_ok());
+        assert!(parse_image("ghcr.io/org/app@sha256:abc").is_err());
+        assert!(parse_image(&format!("ghcr.io/org/app@md5:{digest}")).is_err());
+        assert!(parse_image("").is_err());
+    }
+
     #[test]
     fn workspace_path() {
         let project_args = ProjectArgs {
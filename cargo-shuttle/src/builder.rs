@@ -8,6 +8,9 @@ use shuttle_common::constants::RUNTIME_NAME;
 use tokio::io::AsyncBufReadExt;
 use tracing::{debug, error, info, trace};
 
+/// Target directory (relative to the workspace) of builds made with [`build_workspace_in_docker`]
+pub const DOCKER_TARGET_DIR: &str = "target/shuttle-docker";
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 /// This represents a compiled Shuttle service
 pub struct BuiltService {
@@ -66,7 +69,34 @@ pub async fn build_workspace(
     tx: tokio::sync::mpsc::Sender<String>,
     deployment: bool,
 ) -> anyhow::Result<Vec<BuiltService>> {
-    let project_path = project_path.to_owned();
+    build_workspace_with(project_path, release_mode, tx, deployment, None).await
+}
+
+/// Build the workspace with cargo in a container of `image`, so that the executables link
+/// against the system libraries of that image.
+/// The workspace is mounted at `/app` and built into `target/shuttle-docker`.
+pub async fn build_workspace_in_docker(
+    project_path: &Path,
+    release_mode: bool,
+    tx: tokio::sync::mpsc::Sender<String>,
+    image: &str,
+) -> anyhow::Result<Vec<BuiltService>> {
+    build_workspace_with(project_path, release_mode, tx, false, Some(image)).await
+}
+
+async fn build_workspace_with(
+    project_path: &Path,
+    release_mode: bool,
+    tx: tokio::sync::mpsc::Sender<String>,
+    deployment: bool,
+    docker_image: Option<&str>,
+) -> anyhow::Result<Vec<BuiltService>> {
+    let project_path = match docker_image {
+        // Docker needs absolute paths to mount
+        Some(_) => dunce::canonicalize(project_path)
+            .with_context(|| format!("failed to resolve {}", project_path.display()))?,
+        None => project_path.to_owned(),
+    };
     let manifest_path = project_path.join("Cargo.toml");
     if !manifest_path.exists() {
         bail!("Cargo manifest file not found: {}", manifest_path.display());
@@ -108,12 +138,26 @@ pub async fn build_workspace(
         );
     }
 
+    // Path dependencies outside of the workspace have to be mounted into the build container
+    let external_paths = metadata
+        .packages
+        .iter()
+        .filter(|p| p.source.is_none())
+        .filter_map(|p| p.manifest_path.parent())
+        .map(|dir| dir.as_std_path().to_owned())
+        .filter(|dir| !dir.starts_with(&metadata.workspace_root))
+        .collect::<Vec<_>>();
+
     let services = compile(
         packages,
         release_mode,
         project_path.clone(),
-        metadata.target_directory.clone(),
+        match docker_image {
+            Some(_) => project_path.join(DOCKER_TARGET_DIR),
+            None => metadata.target_directory.clone().into_std_path_buf(),
+        },
         deployment,
+        docker_image.map(|image| (image, external_paths.as_slice())),
         tx.clone(),
     )
     .await?;
@@ -122,6 +166,39 @@ pub async fn build_workspace(
     Ok(services)
 }
 
+/// Name of the container that builds the workspace at `project_path` with [`build_workspace_in_docker`]
+fn docker_build_container(project_path: &Path) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    project_path.hash(&mut hasher);
+
+    format!("shuttle_build_{:x}", hasher.finish())
+}
+
+/// Arguments to run a container as the owner of the workspace, so that files it creates
+/// in the workspace don't end up owned by root
+pub fn docker_user_args(project_path: &Path) -> anyhow::Result<Vec<String>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = std::fs::metadata(project_path)
+            .with_context(|| format!("failed to read {}", project_path.display()))?;
+
+        Ok(vec![
+            "--user".to_owned(),
+            format!("{}:{}", metadata.uid(), metadata.gid()),
+        ])
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = project_path;
+
+        Ok(Vec::new())
+    }
+}
+
 pub async fn async_cargo_metadata(manifest_path: &Path) -> anyhow::Result<Metadata> {
     let metadata = {
         // Modified implementaion of `cargo_metadata::MetadataCommand::exec` (from v0.15.3).
@@ -204,6 +281,7 @@ async fn compile(
     project_path: PathBuf,
     target_path: impl Into<PathBuf>,
     deployment: bool,
+    docker: Option<(&str, &[PathBuf])>,
     tx: tokio::sync::mpsc::Sender<String>,
 ) -> anyhow::Result<Vec<BuiltService>> {
     let manifest_path = project_path.join("Cargo.toml");
@@ -214,11 +292,40 @@ async fn compile(
 
     // TODO?: Use https://crates.io/crates/escargot instead
 
-    let mut cmd = tokio::process::Command::new("cargo");
-    cmd.arg("build")
-        .arg("--manifest-path")
-        .arg(manifest_path)
-        .arg("--color=always") // piping disables auto color, but we want it
+    let mut cmd = match docker {
+        Some((image, external_paths)) => {
+            // A build that was interrupted leaves its container behind
+            let container = docker_build_container(&project_path);
+            crate::remove_docker_container(&container).await?;
+
+            // Mount at the same paths as on the host, so that relative path dependencies resolve
+            let mut cmd = tokio::process::Command::new("docker");
+            cmd.args(["run", "--rm", "--init", "--name", &container])
+                .arg("--workdir")
+                .arg(&project_path);
+            for path in std::iter::once(&project_path).chain(external_paths) {
+                cmd.arg("--volume")
+                    .arg(format!("{}:{}", path.display(), path.display()));
+            }
+            cmd.args(docker_user_args(&project_path)?)
+                .arg("--env")
+                .arg(format!("CARGO_TARGET_DIR={}", target_path.display()))
+                // Keep downloaded crates between builds, in a directory the user can write to
+                .arg("--env")
+                .arg(format!(
+                    "CARGO_HOME={}",
+                    target_path.join("cargo-home").display()
+                ))
+                .args([image, "cargo", "build"]);
+            cmd
+        }
+        None => {
+            let mut cmd = tokio::process::Command::new("cargo");
+            cmd.arg("build").arg("--manifest-path").arg(manifest_path);
+            cmd
+        }
+    };
+    cmd.arg("--color=always") // piping disables auto color, but we want it
         .current_dir(project_path.as_path());
 
     if deployment {
@@ -269,7 +376,10 @@ async fn compile(
             ]
             .iter()
             .collect();
-            path.set_extension(std::env::consts::EXE_EXTENSION);
+            // Executables built in Docker are for Linux, whatever the host is
+            if docker.is_none() {
+                path.set_extension(std::env::consts::EXE_EXTENSION);
+            }
 
             BuiltService {
                 workspace_path: project_path.clone(),
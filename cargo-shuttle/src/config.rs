@@ -1,8 +1,9 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use shuttle_common::constants::API_URL_DEFAULT_BETA;
 use tracing::trace;
@@ -124,6 +125,20 @@ impl ConfigManager for LocalConfigManager {
 pub struct GlobalConfig {
     api_key: Option<String>,
     pub api_url: Option<String>,
+    /// Save crash reports locally (opt-in)
+    pub crash_reports: Option<bool>,
+    /// Named sets of API URL, API key and project, selected with `--profile`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, GlobalConfigProfile>,
+}
+
+/// A named profile in the global config
+#[derive(Deserialize, Serialize, Default)]
+pub struct GlobalConfigProfile {
+    api_key: Option<String>,
+    pub api_url: Option<String>,
+    /// Project to use when no `--name` is given
+    pub project: Option<String>,
 }
 
 impl GlobalConfig {
@@ -153,6 +168,8 @@ pub struct ProjectConfig {
     pub assets: Option<Vec<String>>,
     pub deploy: Option<ProjectDeployConfig>,
     pub build: Option<ProjectBuildConfig>,
+    /// Resources the project expects, checked before deploying, e.g. `postgres = "shared"`.
+    pub resources: Option<BTreeMap<String, String>>,
 }
 /// Deployment command config
 #[derive(Deserialize, Serialize, Default)]
@@ -160,8 +177,13 @@ pub struct ProjectDeployConfig {
     /// Successor to `assets`.
     /// Patterns of ignored files that should be included in deployments.
     pub include: Option<Vec<String>>,
+    /// Patterns of files that should be left out of deployments, even if they are included by other rules.
+    pub exclude: Option<Vec<String>>,
     /// Set to true to deny deployments with uncommited changes. (use `--allow-dirty` to override)
     pub deny_dirty: Option<bool>,
+    /// HTTP path that the service responds to with a success status when it is healthy, e.g. "/health".
+    /// If not set, a deployment is considered healthy as long as the runtime is up.
+    pub health_check_path: Option<String>,
 }
 /// Builder config
 #[derive(Deserialize, Serialize, Default)]
@@ -169,6 +191,8 @@ pub struct ProjectBuildConfig {
     /// Successor to `build_assets`.
     /// Patterns of files that should be copied from the build to the runtime container.
     pub assets: Option<Vec<String>>,
+    /// Cargo features to activate when building the service.
+    pub features: Option<Vec<String>>,
 }
 
 /// .shuttle/config.toml schema (internal project-local config)
@@ -262,6 +286,7 @@ pub struct RequestContext {
     project: Option<Config<LocalConfigManager, ProjectConfig>>,
     project_internal: Option<Config<LocalConfigManager, InternalProjectConfig>>,
     api_url: Option<String>,
+    profile: Option<String>,
 }
 
 impl RequestContext {
@@ -279,6 +304,7 @@ impl RequestContext {
             project: None,
             project_internal: None,
             api_url: None,
+            profile: None,
         })
     }
 
@@ -416,9 +442,41 @@ impl RequestContext {
         self.api_url = api_url;
     }
 
+    /// Use the named profile of the global configuration for the API URL, API key and project.
+    /// Fails if the profile doesn't exist, unless `create` is set (used when logging in).
+    pub fn set_profile(&mut self, profile: Option<String>, create: bool) -> Result<()> {
+        if let Some(ref name) = profile {
+            let profiles = &self.global.as_ref().unwrap().profiles;
+            if !create && !profiles.contains_key(name) {
+                let available = if profiles.is_empty() {
+                    "none".to_owned()
+                } else {
+                    profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                };
+                bail!("unknown profile '{name}'. Available profiles: {available}");
+            }
+        }
+        self.profile = profile;
+
+        Ok(())
+    }
+
+    fn profile(&self) -> Option<&GlobalConfigProfile> {
+        self.profile
+            .as_ref()
+            .and_then(|name| self.global.as_ref().unwrap().profiles.get(name))
+    }
+
+    /// The project of the selected profile, if it has one
+    pub fn profile_project(&self) -> Option<String> {
+        self.profile().and_then(|p| p.project.clone())
+    }
+
     pub fn api_url(&self) -> String {
         if let Some(api_url) = self.api_url.clone() {
             api_url
+        } else if let Some(api_url) = self.profile().and_then(|p| p.api_url.clone()) {
+            api_url
         } else if let Some(api_url) = self.global.as_ref().unwrap().api_url() {
             api_url
         } else {
@@ -427,12 +485,17 @@ impl RequestContext {
     }
 
     /// Get the API key from the `SHUTTLE_API_KEY` env variable, or
-    /// otherwise from the global configuration. Returns an error if
-    /// an API key is not set.
+    /// otherwise from the selected profile or the global configuration.
+    /// Returns an error if an API key is not set.
     pub fn api_key(&self) -> Result<String> {
+        let config_key = match self.profile {
+            // Don't fall back to the default key, it is likely for another environment
+            Some(_) => self.profile().and_then(|p| p.api_key.clone()),
+            None => self.global.as_ref().unwrap().api_key(),
+        };
         match std::env::var("SHUTTLE_API_KEY") {
             Ok(key) => Ok(key),
-            Err(_) => match self.global.as_ref().unwrap().api_key() {
+            Err(_) => match config_key {
                 Some(key) => Ok(key),
                 None => Err(anyhow!(
                     "Configuration file: `{}`",
@@ -458,14 +521,49 @@ impl RequestContext {
             .as_path()
     }
 
-    /// Set the API key to the global configuration. Will persist the file.
+    /// Set the API key to the selected profile or the global configuration. Will persist the file.
     pub fn set_api_key(&mut self, api_key: String) -> Result<()> {
-        self.global.as_mut().unwrap().set_api_key(api_key);
+        let global = self.global.as_mut().unwrap();
+        match self.profile {
+            Some(ref name) => {
+                let profile = global.profiles.entry(name.clone()).or_default();
+                profile.api_key = Some(api_key);
+                // Keep targeting the same API with this profile
+                if profile.api_url.is_none() {
+                    profile.api_url.clone_from(&self.api_url);
+                }
+            }
+            None => {
+                global.set_api_key(api_key);
+            }
+        }
         self.global.save()
     }
 
     pub fn clear_api_key(&mut self) -> Result<()> {
-        self.global.as_mut().unwrap().clear_api_key();
+        let global = self.global.as_mut().unwrap();
+        match self.profile {
+            Some(ref name) => {
+                if let Some(profile) = global.profiles.get_mut(name) {
+                    profile.api_key = None;
+                }
+            }
+            None => global.clear_api_key(),
+        }
+        self.global.save()
+    }
+
+    pub fn crash_reports(&self) -> bool {
+        self.global
+            .as_ref()
+            .unwrap()
+            .crash_reports
+            .unwrap_or_default()
+    }
+
+    /// Enable or disable crash reports in the global configuration. Will persist the file.
+    pub fn set_crash_reports(&mut self, enabled: bool) -> Result<()> {
+        self.global.as_mut().unwrap().crash_reports = Some(enabled);
         self.global.save()
     }
 
@@ -506,6 +604,19 @@ impl RequestContext {
                 .as_ref())
     }
 
+    /// # Panics
+    /// Panics if the project configuration has not been loaded.
+    pub fn exclude(&self) -> Option<&Vec<String>> {
+        self.project
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .deploy
+            .as_ref()
+            .and_then(|d| d.exclude.as_ref())
+    }
+
     /// # Panics
     /// Panics if the project configuration has not been loaded.
     pub fn deny_dirty(&self) -> Option<bool> {
@@ -519,6 +630,50 @@ impl RequestContext {
             .and_then(|d| d.deny_dirty)
     }
 
+    /// Get the health check path from the local project config
+    ///
+    /// # Panics
+    /// Panics if the project configuration has not been loaded.
+    pub fn health_check_path(&self) -> Option<&String> {
+        self.project
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .deploy
+            .as_ref()
+            .and_then(|d| d.health_check_path.as_ref())
+    }
+
+    /// Get the cargo features to build with from the local project config
+    ///
+    /// # Panics
+    /// Panics if the project configuration has not been loaded.
+    pub fn build_features(&self) -> Option<&Vec<String>> {
+        self.project
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .build
+            .as_ref()
+            .and_then(|b| b.features.as_ref())
+    }
+
+    /// Get the resources declared in the local project config
+    ///
+    /// # Panics
+    /// Panics if the project configuration has not been loaded.
+    pub fn declared_resources(&self) -> Option<&BTreeMap<String, String>> {
+        self.project
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .resources
+            .as_ref()
+    }
+
     /// Check if the current project id has been loaded.
     pub fn project_id_found(&self) -> bool {
         self.project_internal
@@ -553,7 +708,7 @@ mod tests {
 
     use crate::{args::ProjectArgs, config::RequestContext};
 
-    use super::{Config, LocalConfigManager, ProjectConfig};
+    use super::{Config, GlobalConfig, GlobalConfigManager, LocalConfigManager, ProjectConfig};
 
     fn path_from_workspace_root(path: &str) -> PathBuf {
         PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
@@ -565,6 +720,54 @@ mod tests {
         config.as_ref().unwrap().name.as_ref().unwrap().to_string()
     }
 
+    #[test]
+    fn profile_overrides_global_config() {
+        let config: GlobalConfig = toml::from_str(
+            r#"
+            api_key = "default-key"
+
+            [profiles.staging]
+            api_url = "https://api.staging.invalid"
+            api_key = "staging-key"
+            project = "my-app"
+
+            [profiles.local]
+            api_url = "http://localhost:8001"
+            "#,
+        )
+        .unwrap();
+        let mut global = Config::new(GlobalConfigManager);
+        global.replace(config);
+        let mut ctx = RequestContext {
+            global,
+            project: None,
+            project_internal: None,
+            api_url: None,
+            profile: None,
+        };
+        assert_eq!(ctx.api_key().unwrap(), "default-key");
+        assert_eq!(ctx.profile_project(), None);
+
+        ctx.set_profile(Some("staging".to_owned()), false).unwrap();
+        assert_eq!(ctx.api_url(), "https://api.staging.invalid");
+        assert_eq!(ctx.api_key().unwrap(), "staging-key");
+        assert_eq!(ctx.profile_project().as_deref(), Some("my-app"));
+
+        // A profile without a key does not use the default key
+        ctx.set_profile(Some("local".to_owned()), false).unwrap();
+        assert!(ctx.api_key().is_err());
+
+        let err = ctx
+            .set_profile(Some("unknown".to_owned()), false)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown profile 'unknown'. Available profiles: local, staging"
+        );
+        // Logging in creates the profile
+        ctx.set_profile(Some("unknown".to_owned()), true).unwrap();
+    }
+
     #[test]
     fn get_local_config_finds_name_in_cargo_toml() {
         let project_args = ProjectArgs {
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::{self, read_to_string};
 use std::num::NonZeroU32;
 use std::sync::atomic::AtomicBool;
@@ -7,14 +8,17 @@ use std::{
 };
 
 use anyhow::{Context, Result};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use gix::clone::PrepareFetch;
 use gix::create::{self, Kind};
 use gix::remote::fetch::Shallow;
 use gix::{open, progress};
+use indoc::{formatdoc, indoc};
 use regex::Regex;
+use serde::Deserialize;
 use shuttle_common::constants::EXAMPLES_README;
 use tempfile::{Builder, TempDir};
-use toml_edit::{value, DocumentMut};
+use toml_edit::{value, DocumentMut, Item, Table};
 use url::Url;
 
 use crate::args::TemplateLocation;
@@ -23,6 +27,8 @@ pub fn generate_project(
     dest: PathBuf,
     name: &str,
     temp_loc: &TemplateLocation,
+    defines: &[(String, String)],
+    interactive: bool,
     no_git: bool,
 ) -> Result<()> {
     println!(r#"Creating project "{name}" in "{}""#, dest.display());
@@ -46,6 +52,8 @@ pub fn generate_project(
     };
 
     // Prepare the template by changing its default contents.
+    fill_placeholders(&path, name, defines, interactive)
+        .context("Failed to fill in the template placeholders")?;
     let crate_name_set = set_crate_name(&path, name)
         .context("Failed to set crate name. No Cargo.toml in template?")?;
     // if the crate name was not updated, set it in Shuttle.toml instead
@@ -217,6 +225,144 @@ enum GitDir {
     Copy,
 }
 
+/// Name of the file where cargo-generate templates declare their placeholders and hooks
+const CARGO_GENERATE_CONFIG: &str = "cargo-generate.toml";
+
+#[derive(Deserialize, Default)]
+struct CargoGenerateConfig {
+    #[serde(default)]
+    placeholders: BTreeMap<String, Placeholder>,
+    hooks: Option<toml::Table>,
+}
+
+#[derive(Deserialize)]
+struct Placeholder {
+    prompt: Option<String>,
+    r#type: Option<String>,
+    choices: Option<Vec<String>>,
+    default: Option<toml::Value>,
+}
+
+/// Replace the `{{ placeholder }}` values in all files of a cargo-generate template.
+/// Placeholders without a `--define` value use their default, or are prompted for if `interactive`.
+fn fill_placeholders(
+    path: &Path,
+    name: &str,
+    defines: &[(String, String)],
+    interactive: bool,
+) -> Result<()> {
+    let config_path = path.join(CARGO_GENERATE_CONFIG);
+    let config: CargoGenerateConfig = if config_path.exists() {
+        toml::from_str(&read_to_string(&config_path)?)
+            .with_context(|| format!("Failed to parse {CARGO_GENERATE_CONFIG}"))?
+    } else {
+        Default::default()
+    };
+
+    // Same built-in placeholders as cargo-generate
+    let mut values = BTreeMap::from([
+        ("project-name".to_owned(), name.to_owned()),
+        ("crate_name".to_owned(), name.replace('-', "_")),
+    ]);
+    values.extend(defines.iter().cloned());
+
+    let theme = ColorfulTheme::default();
+    for (key, placeholder) in config.placeholders {
+        let default = placeholder.default.map(|d| match d {
+            toml::Value::String(s) => s,
+            other => other.to_string(),
+        });
+        let value = match values.remove(&key) {
+            Some(value) => value,
+            None if interactive => {
+                let prompt = placeholder.prompt.unwrap_or_else(|| key.clone());
+                if let Some(ref choices) = placeholder.choices {
+                    let index = Select::with_theme(&theme)
+                        .with_prompt(prompt)
+                        .items(choices)
+                        .default(
+                            default
+                                .as_ref()
+                                .and_then(|d| choices.iter().position(|c| c == d))
+                                .unwrap_or_default(),
+                        )
+                        .interact()?;
+                    choices[index].clone()
+                } else if placeholder.r#type.as_deref() == Some("bool") {
+                    Confirm::with_theme(&theme)
+                        .with_prompt(prompt)
+                        .default(default.as_deref() == Some("true"))
+                        .interact()?
+                        .to_string()
+                } else {
+                    let mut input = Input::with_theme(&theme).with_prompt(prompt);
+                    if let Some(default) = default {
+                        input = input.default(default);
+                    }
+                    input.interact_text()?
+                }
+            }
+            None => default.with_context(|| {
+                format!(
+                    "The template needs a value for '{key}'. Set it with `--define {key}=<value>`"
+                )
+            })?,
+        };
+        if let Some(ref choices) = placeholder.choices {
+            if !choices.contains(&value) {
+                anyhow::bail!(
+                    "'{value}' is not a valid value for '{key}'. Possible values: {}",
+                    choices.join(", ")
+                );
+            }
+        }
+        values.insert(key, value);
+    }
+
+    if let Some(hooks) = config.hooks {
+        if !hooks.is_empty() {
+            println!(
+                "Warning: cargo-generate hooks are not supported and were skipped: {}",
+                hooks.keys().cloned().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+    if config_path.exists() {
+        fs::remove_file(&config_path)?;
+    }
+
+    replace_placeholders(path, &values)
+}
+
+/// Recursively replace `{{key}}` and `{{ key }}` with the values in all text files of a directory
+fn replace_placeholders(dir: &Path, values: &BTreeMap<String, String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let entry_type = entry.file_type()?;
+        if entry_type.is_dir() {
+            if entry.file_name() != ".git" {
+                replace_placeholders(&entry.path(), values)?;
+            }
+        } else if entry_type.is_file() {
+            // Skip binary files
+            let Ok(content) = read_to_string(entry.path()) else {
+                continue;
+            };
+            let mut replaced = content.clone();
+            for (key, value) in values {
+                replaced = replaced
+                    .replace(&format!("{{{{{key}}}}}"), value)
+                    .replace(&format!("{{{{ {key} }}}}"), value);
+            }
+            if replaced != content {
+                fs::write(entry.path(), replaced)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Returns whether the crate name was modified or not
 fn set_crate_name(path: &Path, name: &str) -> Result<bool> {
     let path = path.join("Cargo.toml");
@@ -273,6 +419,166 @@ fn edit_shuttle_toml(path: &Path, set_name: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Web frameworks that an existing project can be adapted from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CargoFramework {
+    Axum,
+    ActixWeb,
+    Rocket,
+}
+
+impl CargoFramework {
+    /// The crate that the framework is detected by
+    pub fn crate_name(&self) -> &'static str {
+        match self {
+            Self::Axum => "axum",
+            Self::ActixWeb => "actix-web",
+            Self::Rocket => "rocket",
+        }
+    }
+
+    /// The Shuttle integration crate for the framework
+    fn shuttle_crate_name(&self) -> &'static str {
+        match self {
+            Self::Axum => "shuttle-axum",
+            Self::ActixWeb => "shuttle-actix-web",
+            Self::Rocket => "shuttle-rocket",
+        }
+    }
+}
+
+/// Turn an existing Axum, Actix Web or Rocket crate into a Shuttle project.
+///
+/// Adds the Shuttle dependencies to Cargo.toml and adds a `#[shuttle_runtime::main]` entrypoint to
+/// `src/main.rs`. The previous entrypoint is kept as `original_main` when it can't be reused as is.
+/// Returns the detected framework and whether the old entrypoint still needs to be migrated by hand.
+pub fn adapt_cargo_project(path: &Path, shuttle_version: &str) -> Result<(CargoFramework, bool)> {
+    let manifest_path = path.join("Cargo.toml");
+    let toml_str = read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut doc = toml_str.parse::<DocumentMut>()?;
+
+    if doc.get("package").is_none() {
+        anyhow::bail!("Only single crates can be adapted. Run this in the crate to deploy instead of the workspace root.");
+    }
+    let deps = doc
+        .entry("dependencies")
+        .or_insert(Item::Table(Table::new()))
+        .as_table_like_mut()
+        .context("Invalid [dependencies] table in Cargo.toml")?;
+    if deps.contains_key("shuttle-runtime") {
+        anyhow::bail!("This crate already depends on shuttle-runtime");
+    }
+    let framework = [
+        CargoFramework::Axum,
+        CargoFramework::ActixWeb,
+        CargoFramework::Rocket,
+    ]
+    .into_iter()
+    .find(|f| deps.contains_key(f.crate_name()))
+    .context("Could not detect a supported framework. Only axum, actix-web and rocket crates can be adapted.")?;
+
+    deps.insert("shuttle-runtime", value(shuttle_version));
+    deps.insert(framework.shuttle_crate_name(), value(shuttle_version));
+    if !deps.contains_key("tokio") {
+        deps.insert("tokio", value("1"));
+    }
+
+    let main_path = path.join("src/main.rs");
+    let main_str = read_to_string(&main_path)
+        .with_context(|| format!("Failed to read {}", main_path.display()))?;
+    let (main_str, needs_migration) = add_shuttle_main(&main_str, framework)?;
+
+    std::fs::write(&manifest_path, doc.to_string())?;
+    std::fs::write(&main_path, main_str)?;
+
+    Ok((framework, needs_migration))
+}
+
+/// Replace the framework's entrypoint attribute with a `#[shuttle_runtime::main]` function
+fn add_shuttle_main(main_str: &str, framework: CargoFramework) -> Result<(String, bool)> {
+    let entrypoint_re = Regex::new(
+        r"(?m)^#\[(?:tokio::main|actix_web::main|actix_rt::main|rocket::main|launch)(?:\([^)]*\))?\]\s*\n(?<sig>(?:pub\s+)?(?<async>async\s+)?fn\s+)(?<name>\w+)\s*\(\)(?<ret>\s*->\s*_)?",
+    )
+    .unwrap();
+    let caps = entrypoint_re
+        .captures(main_str)
+        .context("Could not find the main function of the crate in src/main.rs")?;
+    let range = caps.get(0).unwrap().range();
+    let sig = &caps["sig"];
+
+    // A Rocket `#[launch]` function returns the built Rocket, which can be used directly
+    let reuse_rocket = framework == CargoFramework::Rocket && caps.name("ret").is_some();
+    let (old_entrypoint, shuttle_main) = if reuse_rocket {
+        let name = &caps["name"];
+        let call = if caps.name("async").is_some() {
+            format!("{name}().await")
+        } else {
+            format!("{name}()")
+        };
+        (
+            format!("{sig}{name}() -> rocket::Rocket<rocket::Build>"),
+            formatdoc!(
+                "
+                #[shuttle_runtime::main]
+                async fn main() -> shuttle_rocket::ShuttleRocket {{
+                    Ok({call}.into())
+                }}
+                "
+            ),
+        )
+    } else {
+        let shuttle_main = match framework {
+            CargoFramework::Axum => indoc! {"
+                #[shuttle_runtime::main]
+                async fn main() -> shuttle_axum::ShuttleAxum {
+                    // TODO: Move the router from `original_main` here. Shuttle binds the address.
+                    let router = axum::Router::new();
+
+                    Ok(router.into())
+                }
+            "},
+            CargoFramework::ActixWeb => indoc! {"
+                #[shuttle_runtime::main]
+                async fn main() -> shuttle_actix_web::ShuttleActixWeb<
+                    impl FnOnce(&mut actix_web::web::ServiceConfig) + Send + Clone + 'static,
+                > {
+                    let config = move |_cfg: &mut actix_web::web::ServiceConfig| {
+                        // TODO: Move the services from `original_main` here. Shuttle binds the address.
+                    };
+
+                    Ok(config.into())
+                }
+            "},
+            CargoFramework::Rocket => indoc! {"
+                #[shuttle_runtime::main]
+                async fn main() -> shuttle_rocket::ShuttleRocket {
+                    // TODO: Move the routes from `original_main` here. Shuttle binds the address.
+                    let rocket = rocket::build();
+
+                    Ok(rocket.into())
+                }
+            "},
+        };
+        (
+            format!("#[allow(dead_code)]\n{sig}original_main()"),
+            shuttle_main.to_owned(),
+        )
+    };
+
+    let mut out = String::with_capacity(main_str.len() + shuttle_main.len());
+    out.push_str(&main_str[..range.start]);
+    out.push_str(&old_entrypoint);
+    out.push_str(&main_str[range.end..]);
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push('\n');
+    out.push_str(&shuttle_main);
+
+    Ok((out, !reuse_rocket))
+}
+
 /// Adds any missing recommended ignore rules to an ignore file (usually .gitignore)
 pub fn create_or_update_ignore_file(path: &Path) -> Result<()> {
     let mut contents = std::fs::read_to_string(path).unwrap_or_default();
@@ -348,4 +654,137 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[test]
+    fn fill_placeholders_works() {
+        let temp_dir = Builder::new()
+            .prefix("shuttle-placeholders-test")
+            .tempdir()
+            .unwrap();
+        let path = temp_dir.path();
+        fs::write(
+            path.join(CARGO_GENERATE_CONFIG),
+            indoc! {r#"
+                [placeholders.database]
+                type = "string"
+                prompt = "Which database?"
+                choices = ["postgres", "mongodb"]
+                default = "postgres"
+
+                [placeholders.auth]
+                type = "bool"
+                default = false
+            "#},
+        )
+        .unwrap();
+        fs::write(
+            path.join("main.rs"),
+            "// {{project-name}} uses {{ database }}, auth: {{auth}}",
+        )
+        .unwrap();
+
+        let defines = vec![("database".to_owned(), "mongodb".to_owned())];
+        fill_placeholders(path, "my-app", &defines, false).unwrap();
+
+        assert_eq!(
+            read_to_string(path.join("main.rs")).unwrap(),
+            "// my-app uses mongodb, auth: false"
+        );
+        assert!(!path.join(CARGO_GENERATE_CONFIG).exists());
+
+        // Values outside of the choices are rejected
+        fs::write(
+            path.join(CARGO_GENERATE_CONFIG),
+            "[placeholders.database]\nchoices = [\"postgres\"]",
+        )
+        .unwrap();
+        let defines = vec![("database".to_owned(), "mysql".to_owned())];
+        assert!(fill_placeholders(path, "my-app", &defines, false).is_err());
+    }
+
+    #[test]
+    fn add_shuttle_main_reuses_rocket_launch() {
+        let main = indoc! {r#"
+            #[macro_use]
+            extern crate rocket;
+
+            #[get("/")]
+            fn index() -> &'static str {
+                "Hello, world!"
+            }
+
+            #[launch]
+            fn rocket() -> _ {
+                rocket::build().mount("/", routes![index])
+            }
+        "#};
+        let (out, needs_migration) = add_shuttle_main(main, CargoFramework::Rocket).unwrap();
+
+        assert!(!needs_migration);
+        assert!(!out.contains("#[launch]"));
+        assert!(out.contains("fn rocket() -> rocket::Rocket<rocket::Build> {"));
+        assert!(out.contains("Ok(rocket().into())"));
+    }
+
+    #[test]
+    fn add_shuttle_main_keeps_original_main() {
+        let main = indoc! {r#"
+            use axum::{routing::get, Router};
+
+            #[tokio::main]
+            async fn main() {
+                let app = Router::new().route("/", get(|| async { "Hello, World!" }));
+                let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+                axum::serve(listener, app).await.unwrap();
+            }
+        "#};
+        let (out, needs_migration) = add_shuttle_main(main, CargoFramework::Axum).unwrap();
+
+        assert!(needs_migration);
+        assert!(!out.contains("#[tokio::main]"));
+        assert!(out.contains("#[allow(dead_code)]\nasync fn original_main() {"));
+        assert!(out.contains("async fn main() -> shuttle_axum::ShuttleAxum {"));
+
+        assert!(add_shuttle_main("fn main() {}", CargoFramework::Axum).is_err());
+    }
+
+    #[test]
+    fn adapt_cargo_project_adds_dependencies() {
+        let temp_dir = Builder::new()
+            .prefix("shuttle-adapt-test")
+            .tempdir()
+            .unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            indoc! {r#"
+                [package]
+                name = "hello"
+                version = "0.1.0"
+                edition = "2021"
+
+                [dependencies]
+                actix-web = "4"
+            "#},
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("src/main.rs"),
+            "#[actix_web::main]\nasync fn main() -> std::io::Result<()> {\n    Ok(())\n}\n",
+        )
+        .unwrap();
+
+        let (framework, _) = adapt_cargo_project(temp_dir.path(), "0.51.0").unwrap();
+        assert_eq!(framework, CargoFramework::ActixWeb);
+
+        let manifest = read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+        assert!(manifest.contains(r#"shuttle-runtime = "0.51.0""#));
+        assert!(manifest.contains(r#"shuttle-actix-web = "0.51.0""#));
+        assert!(manifest.contains(r#"tokio = "1""#));
+
+        // Running it again is refused
+        assert!(adapt_cargo_project(temp_dir.path(), "0.51.0").is_err());
+
+        temp_dir.close().unwrap();
+    }
 }
@@ -3,6 +3,7 @@ pub mod builder;
 pub mod config;
 mod init;
 mod provisioner_server;
+mod report;
 mod util;
 
 use std::collections::{BTreeMap, HashMap};
@@ -14,6 +15,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{anyhow, bail, Context, Result};
+use cargo_metadata::Package;
 use chrono::Utc;
 use clap::{parser::ValueSource, CommandFactory, FromArgMatches};
 use crossterm::style::Stylize;
@@ -23,28 +25,37 @@ use git2::Repository;
 use globset::{Glob, GlobSetBuilder};
 use ignore::overrides::OverrideBuilder;
 use ignore::WalkBuilder;
-use indicatif::ProgressBar;
+use indicatif::{MultiProgress, ProgressBar};
 use indoc::formatdoc;
 use reqwest::header::HeaderMap;
 use shuttle_api_client::ShuttleApiClient;
 use shuttle_common::{
     constants::{
-        headers::X_CARGO_SHUTTLE_VERSION, API_URL_DEFAULT_BETA, EXAMPLES_REPO, RUNTIME_NAME,
-        STORAGE_DIRNAME, TEMPLATES_SCHEMA_VERSION,
+        headers::X_CARGO_SHUTTLE_VERSION, API_URL_DEFAULT_BETA, CREATE_SERVICE_BODY_LIMIT,
+        EXAMPLES_REPO, RUNTIME_NAME, SHUTTLE_CONSOLE_URL, STORAGE_DIRNAME,
+        TEMPLATES_SCHEMA_VERSION,
     },
     models::{
         auth::{KeyMessage, TokenMessage},
+        certificate::UpdateCertificateRequest,
         deployment::{
             BuildArgs, BuildArgsRust, BuildMeta, DeploymentRequest, DeploymentRequestBuildArchive,
             DeploymentRequestImage, DeploymentResponse, DeploymentState, Environment,
-            GIT_STRINGS_MAX_LENGTH,
         },
         error::ApiError,
-        log::LogItem,
-        project::ProjectUpdateRequest,
-        resource::ResourceType,
+        log::{strip_ansi, LogItem, LogSearchRequest, LogSinkCreateRequest, LogSinkKind},
+        project::{
+            BasicAuthCredentials, ProjectAccessRulesRequest, ProjectEvent, ProjectSchedule,
+            ProjectUpdateRequest,
+        },
+        resource::{ResourceExportBundle, ResourceType, SecretsUpdateRequest},
+        user::{Subscription, SubscriptionType},
+    },
+    secrets::SecretStore,
+    tables::{
+        deployments_table, get_certificates_table, get_log_sinks_table, get_projects_table,
+        get_resource_history_table, get_resource_tables, get_secrets_table,
     },
-    tables::{deployments_table, get_certificates_table, get_projects_table, get_resource_tables},
 };
 use strum::{EnumMessage, VariantArray};
 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -56,19 +67,26 @@ use zip::write::FileOptions;
 
 use crate::args::{
     CertificateCommand, ConfirmationArgs, DeployArgs, DeploymentCommand, GenerateCommand, InitArgs,
-    LoginArgs, LogoutArgs, LogsArgs, ProjectCommand, ProjectUpdateCommand, ResourceCommand,
-    SecretsArgs, TableArgs, TemplateLocation,
+    LogSinkCommand, LoginArgs, LogoutArgs, LogsArgs, LogsCommand, LogsOutput, ProjectCommand,
+    ProjectListSort, ProjectUpdateCommand, ResourceCommand, SecretsCommand, TableArgs,
+    TelemetryCommand, TemplateLocation,
+};
+pub use crate::args::{Command, ProjectArgs, RunArgs, SecretsArgs, ShuttleArgs};
+use crate::builder::{
+    async_cargo_metadata, build_workspace, build_workspace_in_docker, docker_user_args,
+    find_shuttle_packages, BuiltService,
 };
-pub use crate::args::{Command, ProjectArgs, RunArgs, ShuttleArgs};
-use crate::builder::{async_cargo_metadata, build_workspace, find_shuttle_packages, BuiltService};
 use crate::config::RequestContext;
 use crate::provisioner_server::{ProvApiState, ProvisionerServer};
 use crate::util::{
     check_and_warn_runtime_version, generate_completions, generate_manpage, get_templates_schema,
-    is_dirty, open_gh_issue, read_ws_until_text, update_cargo_shuttle,
+    git_build_meta, is_dirty, open_gh_issue, read_ws_until_text, update_cargo_shuttle,
+    watch_workspace,
 };
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Image used to build and run services with `cargo shuttle run --docker`
+const DOCKER_RUN_IMAGE: &str = "docker.io/library/rust:1-bookworm";
 
 /// Returns the args and whether the PATH arg of the init command was explicitly given
 pub fn parse_args() -> (ShuttleArgs, bool) {
@@ -100,7 +118,7 @@ pub fn setup_tracing(debug: bool) {
         .init();
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum Binary {
     CargoShuttle,
     Shuttle,
@@ -132,11 +150,7 @@ impl Shuttle {
         })
     }
 
-    pub async fn run(mut self, args: ShuttleArgs, provided_path_to_init: bool) -> Result<()> {
-        if matches!(args.cmd, Command::Resource(ResourceCommand::Dump { .. })) {
-            bail!("This command is not yet supported on the NEW platform (shuttle.dev).");
-        }
-
+    pub async fn run(mut self, mut args: ShuttleArgs, provided_path_to_init: bool) -> Result<()> {
         if let Some(ref url) = args.api_url {
             if url != API_URL_DEFAULT_BETA {
                 eprintln!(
@@ -149,6 +163,15 @@ impl Shuttle {
             }
         }
         self.ctx.set_api_url(args.api_url);
+        // Logging in with a new profile creates it
+        let create_profile = matches!(args.cmd, Command::Login(..));
+        self.ctx.set_profile(args.profile, create_profile)?;
+        if args.project_args.name_or_id.is_none() {
+            args.project_args.name_or_id = self.ctx.profile_project();
+        }
+        if self.ctx.crash_reports() {
+            crate::report::install_panic_hook();
+        }
 
         // All commands that call the API
         if matches!(
@@ -162,7 +185,9 @@ impl Shuttle {
                 | Command::Deployment(..)
                 | Command::Resource(..)
                 | Command::Certificate(..)
+                | Command::Secrets(..)
                 | Command::Project(..)
+                | Command::Clean { .. }
         ) || (
             // project linking on beta requires api client
             // TODO: refactor so that beta local run does not need to know project id / always uses crate name ???
@@ -186,19 +211,30 @@ impl Shuttle {
         // All commands that need to know which project is being handled
         if matches!(
             args.cmd,
-            Command::Deploy(..)
-                | Command::Deployment(..)
+            // deploying all services uses a project per service, and a dry run only packs files
+            Command::Deploy(DeployArgs {
+                all: false,
+                dry_run: false,
+                ..
+            }) | Command::Deployment(..)
                 | Command::Resource(..)
                 | Command::Certificate(..)
+                | Command::Secrets(..)
                 | Command::Project(
                     // ProjectCommand::List does not need to know which project we are in
                     ProjectCommand::Create
                         | ProjectCommand::Update(..)
                         | ProjectCommand::Status { .. }
+                        | ProjectCommand::Stats
+                        | ProjectCommand::Usage
+                        | ProjectCommand::Rename { .. }
+                        | ProjectCommand::Protect { .. }
+                        | ProjectCommand::Schedule { .. }
                         | ProjectCommand::Delete { .. }
                         | ProjectCommand::Link
                 )
                 | Command::Logs { .. }
+                | Command::Clean { .. }
         ) {
             // Command::Run only uses load_local (below) instead of load_project since it does not target a project in the API
             self.load_project(
@@ -211,7 +247,11 @@ impl Shuttle {
             .await?;
         }
 
-        match args.cmd {
+        // Local runs don't depend on the platform
+        let uses_platform =
+            self.client.is_some() && !args.offline && !matches!(args.cmd, Command::Run(..));
+
+        let res = match args.cmd {
             Command::Init(init_args) => {
                 self.init(
                     init_args,
@@ -230,51 +270,160 @@ impl Shuttle {
             Command::Account => self.account().await,
             Command::Login(login_args) => self.login(login_args, args.offline).await,
             Command::Logout(logout_args) => self.logout(logout_args).await,
-            Command::Feedback => open_gh_issue(),
+            Command::Clean {
+                deep,
+                confirmation: ConfirmationArgs { yes },
+            } => self.clean(deep, yes).await,
+            Command::Feedback { attach_report } => open_gh_issue(attach_report),
+            Command::Telemetry(cmd) => self.telemetry(cmd),
             Command::Run(run_args) => {
                 self.ctx.load_local(&args.project_args)?;
                 self.local_run(run_args, args.debug).await
             }
-            Command::Deploy(deploy_args) => self.deploy(deploy_args).await,
+            Command::Deploy(deploy_args) => {
+                if deploy_args.all || deploy_args.dry_run {
+                    self.ctx.load_local(&args.project_args)?;
+                }
+                self.deploy(deploy_args).await
+            }
             Command::Logs(logs_args) => self.logs(logs_args).await,
             Command::Deployment(cmd) => match cmd {
                 DeploymentCommand::List { page, limit, table } => {
                     self.deployments_list(page, limit, table).await
                 }
                 DeploymentCommand::Status { id } => self.deployment_get(id).await,
+                DeploymentCommand::Follow { id, raw } => self.deployment_follow(id, raw).await,
                 DeploymentCommand::Redeploy { id } => self.deployment_redeploy(id).await,
+                DeploymentCommand::Promote { id, to } => self.deployment_promote(id, to).await,
+                DeploymentCommand::Diff { from, to } => self.deployment_diff(from, to).await,
+                DeploymentCommand::Cancel { id } => self.deployment_cancel(id).await,
                 DeploymentCommand::Stop => self.stop().await,
             },
             Command::Resource(cmd) => match cmd {
                 ResourceCommand::List {
                     table,
                     show_secrets,
-                } => self.resources_list(table, show_secrets).await,
+                    confirmation: ConfirmationArgs { yes },
+                } => self.resources_list(table, show_secrets, yes).await,
                 ResourceCommand::Delete {
                     resource_type,
                     confirmation: ConfirmationArgs { yes },
                 } => self.resource_delete(&resource_type, yes).await,
-                ResourceCommand::Dump { resource_type } => self.resource_dump(&resource_type).await,
+                ResourceCommand::Rotate {
+                    resource_type,
+                    confirmation: ConfirmationArgs { yes },
+                } => self.resource_rotate(&resource_type, yes).await,
+                ResourceCommand::History {
+                    resource_type,
+                    table,
+                } => self.resource_history(&resource_type, table).await,
+                ResourceCommand::Export { output } => self.resources_export(output).await,
+                ResourceCommand::Import {
+                    file,
+                    confirmation: ConfirmationArgs { yes },
+                } => self.resources_import(file, yes).await,
+                ResourceCommand::Dump {
+                    resource_type,
+                    output,
+                    timeout,
+                } => {
+                    self.resource_dump(&resource_type, output, Duration::from_secs(timeout))
+                        .await
+                }
+                ResourceCommand::Restore {
+                    resource_type,
+                    file,
+                    timeout,
+                    confirmation: ConfirmationArgs { yes },
+                } => {
+                    self.resource_restore(&resource_type, file, Duration::from_secs(timeout), yes)
+                        .await
+                }
             },
             Command::Certificate(cmd) => match cmd {
                 CertificateCommand::Add { domain } => self.add_certificate(domain).await,
+                CertificateCommand::Verify { domain, wait } => {
+                    self.verify_domain(domain, wait).await
+                }
                 CertificateCommand::List { table } => self.list_certificates(table).await,
+                CertificateCommand::Update {
+                    domain,
+                    hsts,
+                    http_path,
+                    no_http_paths,
+                } => {
+                    let http_paths = if no_http_paths {
+                        Some(Vec::new())
+                    } else {
+                        http_path
+                    };
+                    self.update_certificate(domain, hsts, http_paths).await
+                }
                 CertificateCommand::Delete {
                     domain,
                     confirmation: ConfirmationArgs { yes },
                 } => self.delete_certificate(domain, yes).await,
             },
+            Command::Secrets(cmd) => match cmd {
+                SecretsCommand::List { table } => self.secrets_list(table).await,
+                SecretsCommand::Set { key, value } => self.secrets_set(key, value).await,
+                SecretsCommand::Unset { key } => self.secrets_unset(key).await,
+                SecretsCommand::Pull {
+                    output,
+                    confirmation: ConfirmationArgs { yes },
+                } => self.secrets_pull(output, yes).await,
+            },
             Command::Project(cmd) => match cmd {
                 ProjectCommand::Create => self.project_create().await,
                 ProjectCommand::Update(cmd) => match cmd {
                     ProjectUpdateCommand::Name { name } => self.project_rename(name).await,
                 },
-                ProjectCommand::Status => self.project_status().await,
-                ProjectCommand::List { table, .. } => self.projects_list(table).await,
+                ProjectCommand::Status { follow } => self.project_status(follow).await,
+                ProjectCommand::Stats => self.project_stats().await,
+                ProjectCommand::Usage => self.project_usage().await,
+                ProjectCommand::Rename { name } => self.project_rename(name).await,
+                ProjectCommand::Schedule {
+                    stop,
+                    start,
+                    remove,
+                } => self.project_schedule(stop, start, remove).await,
+                ProjectCommand::Protect {
+                    allow_ip,
+                    basic_auth,
+                    remove,
+                } => self.project_protect(allow_ip, basic_auth, remove).await,
+                ProjectCommand::List { state, sort, table } => {
+                    self.projects_list(state, sort, table).await
+                }
                 ProjectCommand::Delete(ConfirmationArgs { yes }) => self.project_delete(yes).await,
                 ProjectCommand::Link => Ok(()), // logic is done in `load_local`
             },
             Command::Upgrade { preview } => update_cargo_shuttle(preview).await,
+        };
+
+        // Server errors might be caused by an incident on the platform
+        let server_error = res.as_ref().is_err_and(|e| {
+            e.chain()
+                .filter_map(|e| e.downcast_ref::<ApiError>())
+                .any(|e| e.status().is_server_error())
+        });
+        if server_error && uses_platform {
+            self.print_platform_status().await;
+        }
+
+        res
+    }
+
+    /// Show a banner if the platform is having issues, since that might be why a command failed
+    async fn print_platform_status(&self) {
+        let client = self.client.as_ref().unwrap();
+        match client.get_platform_status().await {
+            Ok(status) if !status.is_healthy() => {
+                eprintln!();
+                eprint!("{}", status.to_string_colored());
+            }
+            Ok(_) => (),
+            Err(e) => debug!(error = %e, "failed to get platform status"),
         }
     }
 
@@ -289,6 +438,10 @@ impl Shuttle {
         provided_path_to_init: bool,
         offline: bool,
     ) -> Result<()> {
+        if args.from_cargo {
+            return Shuttle::init_from_cargo(&args.path);
+        }
+
         // Turns the template or git args (if present) to a repo+folder.
         let git_template = args.git_template()?;
         let no_git = args.no_git;
@@ -527,6 +680,8 @@ impl Shuttle {
                 .as_ref()
                 .expect("to have a project name provided"),
             &template,
+            &args.define,
+            interactive,
             no_git,
         )?;
         println!();
@@ -571,6 +726,34 @@ impl Shuttle {
         Ok(())
     }
 
+    /// Add Shuttle to an existing project instead of generating one from a template
+    fn init_from_cargo(path: &Path) -> Result<()> {
+        let (framework, needs_migration) = crate::init::adapt_cargo_project(path, crate::VERSION)?;
+        crate::init::create_or_update_ignore_file(&path.join(".gitignore"))
+            .context("Failed to update .gitignore file")?;
+
+        println!(
+            r#"Adapted the {} project in "{}" to run on Shuttle."#,
+            framework.crate_name(),
+            path.display()
+        );
+        if needs_migration {
+            println!(
+                "{}",
+                formatdoc! {"
+                    Note:
+                        The previous entrypoint was kept as `original_main` in src/main.rs.
+                        Move the app setup from it into the `#[shuttle_runtime::main]` function, then remove it."
+                }
+                .yellow(),
+            );
+        }
+        println!("Run `shuttle run` to run the app locally.");
+        println!("Run `shuttle deploy` to deploy it to Shuttle.");
+
+        Ok(())
+    }
+
     /// Return value: true -> success or unknown. false -> try again.
     async fn check_project_name(&self, project_args: &mut ProjectArgs, name: String) -> bool {
         let client = self.client.as_ref().unwrap();
@@ -730,6 +913,11 @@ impl Shuttle {
         let client = self.client.as_ref().unwrap();
         let user = client.get_current_user().await?;
         print!("{}", user.to_string_colored());
+        // project usage is not part of the user response, so it is looked up separately
+        match client.get_projects_list().await {
+            Ok(r) => println!("  Projects: {}", r.projects.len()),
+            Err(e) => debug!(error = ?e, "failed to get projects"),
+        }
 
         Ok(())
     }
@@ -751,7 +939,8 @@ impl Shuttle {
                         .interact()?
                 } else {
                     // device auth flow via Shuttle Console
-                    self.device_auth(login_args.console_url).await?
+                    self.device_auth(login_args.console_url, !login_args.device)
+                        .await?
                 }
             }
         };
@@ -775,7 +964,7 @@ impl Shuttle {
         Ok(())
     }
 
-    async fn device_auth(&self, console_url: String) -> Result<String> {
+    async fn device_auth(&self, console_url: String, open_browser: bool) -> Result<String> {
         let client = self.client.as_ref().unwrap();
 
         // should not have trailing slash
@@ -803,9 +992,14 @@ impl Shuttle {
         let token = serde_json::from_str::<TokenMessage>(&token)?.token;
 
         let url = &format!("{}/device-auth?token={}", console_url, token);
-        let _ = webbrowser::open(url);
-        println!("Complete login in Shuttle Console to authenticate CLI.");
-        println!("If your browser did not automatically open, go to {url}");
+        if open_browser {
+            let _ = webbrowser::open(url);
+            println!("Complete login in Shuttle Console to authenticate CLI.");
+            println!("If your browser did not automatically open, go to {url}");
+        } else {
+            println!("To authenticate CLI, open this URL on any device and complete login in Shuttle Console:");
+            println!("{url}");
+        }
         println!();
         println!("{}", format!("Token: {token}").bold());
         println!();
@@ -882,52 +1076,156 @@ impl Shuttle {
         Ok(())
     }
 
+    async fn clean(&self, deep: bool, no_confirm: bool) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+
+        let cache = client.get_build_cache(pid).await?;
+        println!("{}", "Build cache:".bold());
+        print!("{}", cache.to_string_colored());
+        let to_clear = if deep {
+            "target directory and crate registry"
+        } else {
+            "target directory"
+        };
+        if !no_confirm
+            && !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Clear the {to_clear}?"))
+                .default(false)
+                .interact()?
+        {
+            return Ok(());
+        }
+
+        let freed = client.clean_build_cache(pid, deep).await?;
+        println!("{}", "Freed:".bold());
+        print!("{}", freed.to_string_colored());
+
+        Ok(())
+    }
+
     async fn logs(&self, args: LogsArgs) -> Result<()> {
+        if let Some(LogsCommand::Sink(cmd)) = args.cmd {
+            return match cmd {
+                LogSinkCommand::Add { kind, url, header } => {
+                    self.log_sink_add(kind, url, header).await
+                }
+                LogSinkCommand::List { table } => self.log_sinks_list(table).await,
+                LogSinkCommand::Remove { id } => self.log_sink_remove(id).await,
+            };
+        }
         if args.follow {
             eprintln!("Streamed logs are not yet supported on the shuttle.dev platform.");
             return Ok(());
         }
-        if args.tail.is_some() | args.head.is_some() {
-            eprintln!("Fetching log ranges are not yet supported on the shuttle.dev platform.");
-            return Ok(());
-        }
         let client = self.client.as_ref().unwrap();
         let pid = self.ctx.project_id();
-        let logs = if args.all_deployments {
-            client.get_project_logs(pid).await?.logs
+        let deployment_id = if args.all_deployments {
+            None
+        } else if args.latest {
+            // Find latest deployment (not always an active one)
+            let deployments = client.get_deployments(pid, 1, 1).await?.deployments;
+            let Some(most_recent) = deployments.into_iter().next() else {
+                println!("No deployments found");
+                return Ok(());
+            };
+            eprintln!("Getting logs from: {}", most_recent.id);
+            Some(most_recent.id)
+        } else if let Some(id) = args.id {
+            Some(id)
         } else {
-            let id = if args.latest {
-                // Find latest deployment (not always an active one)
-                let deployments = client.get_deployments(pid, 1, 1).await?.deployments;
-                let Some(most_recent) = deployments.into_iter().next() else {
-                    println!("No deployments found");
-                    return Ok(());
-                };
-                eprintln!("Getting logs from: {}", most_recent.id);
-                most_recent.id
-            } else if let Some(id) = args.id {
-                id
-            } else {
-                let Some(current) = client.get_current_deployment(pid).await? else {
-                    println!("No deployments found");
-                    return Ok(());
-                };
-                eprintln!("Getting logs from: {}", current.id);
-                current.id
+            let Some(current) = client.get_current_deployment(pid).await? else {
+                println!("No deployments found");
+                return Ok(());
             };
-            client.get_deployment_logs(pid, &id).await?.logs
+            eprintln!("Getting logs from: {}", current.id);
+            Some(current.id)
+        };
+        let logs = match (args.grep, deployment_id) {
+            (Some(query), deployment_id) => {
+                let req = LogSearchRequest {
+                    query,
+                    regex: args.regex,
+                    deployment_id,
+                    since: args.since,
+                    until: args.until,
+                    limit: args
+                        .tail
+                        .map(i32::try_from)
+                        .transpose()
+                        .context("--tail is too large for a log search")?,
+                };
+                client.search_logs(pid, &req).await?.logs
+            }
+            (None, None) => client.get_project_logs(pid).await?.logs,
+            (None, Some(id)) if args.build => {
+                client.get_deployment_build_logs(pid, &id).await?.logs
+            }
+            (None, Some(id)) => client.get_deployment_logs(pid, &id).await?.logs,
         };
+        // the platform returns all lines, so ranges are cut out here
+        let logs = slice_logs(&logs, args.head, args.tail);
+        if args.output == LogsOutput::Json {
+            for log in logs {
+                println!("{}", serde_json::to_string(log)?);
+            }
+            return Ok(());
+        }
         for log in logs {
-            if args.raw {
-                println!("{}", log.line);
-            } else {
-                println!("{log}");
+            match (args.raw, args.no_color) {
+                (true, false) => println!("{}", log.line),
+                (true, true) => println!("{}", strip_ansi(&log.line)),
+                (false, _) => println!("{}", log.format(args.timestamps, !args.no_color)),
             }
         }
 
         Ok(())
     }
 
+    async fn log_sink_add(
+        &self,
+        kind: LogSinkKind,
+        url: String,
+        headers: Vec<(String, String)>,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let sink = client
+            .add_log_sink(
+                self.ctx.project_id(),
+                LogSinkCreateRequest {
+                    kind,
+                    url,
+                    headers: headers.into_iter().collect(),
+                },
+            )
+            .await?;
+
+        println!("Added {} log sink {} for {}", sink.kind, sink.id, sink.url);
+
+        Ok(())
+    }
+
+    async fn log_sinks_list(&self, table_args: TableArgs) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let sinks = client.get_log_sinks(self.ctx.project_id()).await?.sinks;
+
+        if sinks.is_empty() {
+            println!("No log sinks found. Add one with `shuttle logs sink add`.");
+        } else {
+            println!("{}", get_log_sinks_table(&sinks, table_args.raw));
+        }
+
+        Ok(())
+    }
+
+    async fn log_sink_remove(&self, id: String) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let msg = client.delete_log_sink(self.ctx.project_id(), &id).await?;
+        println!("{msg}");
+
+        Ok(())
+    }
+
     async fn deployments_list(&self, page: u32, limit: u32, table_args: TableArgs) -> Result<()> {
         let client = self.client.as_ref().unwrap();
         if limit == 0 {
@@ -983,6 +1281,28 @@ impl Shuttle {
         Ok(())
     }
 
+    async fn deployment_follow(&self, deployment_id: Option<String>, raw: bool) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+
+        let id = match deployment_id {
+            Some(id) => id,
+            None => {
+                // Find latest deployment (not always an active one)
+                let deployments = client.get_deployments(pid, 1, 1).await?.deployments;
+                let Some(most_recent) = deployments.into_iter().next() else {
+                    println!("No deployments found");
+                    return Ok(());
+                };
+                eprintln!("Following deployment: {}", most_recent.id);
+                most_recent.id
+            }
+        };
+
+        self.track_deployment_status_and_print_logs_on_fail(pid, &id, raw)
+            .await
+    }
+
     async fn deployment_redeploy(&self, deployment_id: Option<String>) -> Result<()> {
         let client = self.client.as_ref().unwrap();
 
@@ -1006,17 +1326,178 @@ impl Shuttle {
         Ok(())
     }
 
-    async fn resources_list(&self, table_args: TableArgs, show_secrets: bool) -> Result<()> {
+    async fn deployment_promote(&self, deployment_id: Option<String>, to: String) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+
+        let pid = self.ctx.project_id();
+        let deployment_id = match deployment_id {
+            Some(id) => id,
+            None => {
+                let d = client.get_current_deployment(pid).await?;
+                let Some(d) = d else {
+                    println!("No deployment found");
+                    return Ok(());
+                };
+                d.id
+            }
+        };
+        let target_pid = if to.starts_with("proj_") {
+            to
+        } else {
+            client
+                .get_projects_list()
+                .await?
+                .projects
+                .into_iter()
+                .find(|p| p.name == to)
+                .with_context(|| format!("No project named '{to}' found"))?
+                .id
+        };
+        if target_pid == pid {
+            bail!("Can't promote a deployment to the project it is in");
+        }
+
+        eprintln!("Promoting {deployment_id} to {target_pid}");
+        let deployment = client
+            .promote_deployment(pid, &deployment_id, &target_pid)
+            .await?;
+
+        self.track_deployment_status_and_print_logs_on_fail(&target_pid, &deployment.id, false)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn deployment_diff(&self, from: String, to: Option<String>) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+
+        let from = client.get_deployment(pid, &from).await?;
+        let to = match to {
+            Some(id) => client.get_deployment(pid, &id).await?,
+            None => {
+                let deployments = client.get_deployments(pid, 1, 1).await?.deployments;
+                let Some(most_recent) = deployments.into_iter().next() else {
+                    println!("No deployments found");
+                    return Ok(());
+                };
+                most_recent
+            }
+        };
+
+        println!(
+            "Comparing deployment {} to {}",
+            from.id.as_str().bold(),
+            to.id.as_str().bold()
+        );
+        let changes = from.diff(&to);
+        if changes.is_empty() {
+            println!("No changes found");
+        }
+        for change in changes {
+            println!(
+                "  {}: {} -> {}",
+                change.field,
+                change.from.dark_red(),
+                change.to.green()
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn deployment_cancel(&self, deployment_id: Option<String>) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+
+        let pid = self.ctx.project_id();
+        let deployment = match deployment_id {
+            Some(id) => client.get_deployment(pid, &id).await?,
+            None => {
+                let d = client.get_current_deployment(pid).await?;
+                let Some(d) = d else {
+                    println!("No deployment found");
+                    return Ok(());
+                };
+                d
+            }
+        };
+        if !matches!(
+            deployment.state,
+            DeploymentState::Pending | DeploymentState::Building
+        ) {
+            println!(
+                "Deployment {} is {} and can't be cancelled",
+                deployment.id, deployment.state
+            );
+            return Ok(());
+        }
+        let deployment = client.cancel_deployment(pid, &deployment.id).await?;
+        println!("{}", deployment.to_string_colored());
+
+        Ok(())
+    }
+
+    async fn resources_list(
+        &self,
+        table_args: TableArgs,
+        show_secrets: bool,
+        no_confirm: bool,
+    ) -> Result<()> {
+        if show_secrets
+            && !no_confirm
+            && !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Show passwords and other secrets of the resources in plain text?")
+                .default(false)
+                .interact()
+                .unwrap()
+        {
+            return Ok(());
+        }
+
         let client = self.client.as_ref().unwrap();
         let pid = self.ctx.project_id();
         let resources = client.get_service_resources(pid).await?.resources;
-        let table = get_resource_tables(resources.as_slice(), pid, table_args.raw, show_secrets);
+        // Usage is not reported for all resources, so don't fail the listing if it is missing
+        let usage = match client.get_service_resources_usage(pid).await {
+            Ok(r) => r.usage,
+            Err(e) => {
+                debug!(error = %e, "failed to get resource usage");
+                Vec::new()
+            }
+        };
+        let table = get_resource_tables(
+            resources.as_slice(),
+            usage.as_slice(),
+            pid,
+            table_args.raw,
+            show_secrets,
+        );
 
         println!("{table}");
 
         Ok(())
     }
 
+    async fn resource_history(
+        &self,
+        resource_type: &ResourceType,
+        table_args: TableArgs,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let history = client
+            .get_service_resource_history(self.ctx.project_id(), resource_type)
+            .await?
+            .history;
+
+        if history.is_empty() {
+            println!("No history found for {resource_type}");
+        } else {
+            println!("{}", get_resource_history_table(&history, table_args.raw));
+        }
+
+        Ok(())
+    }
+
     async fn resource_delete(&self, resource_type: &ResourceType, no_confirm: bool) -> Result<()> {
         let client = self.client.as_ref().unwrap();
 
@@ -1061,36 +1542,304 @@ impl Shuttle {
         Ok(())
     }
 
-    async fn resource_dump(&self, _resource_type: &ResourceType) -> Result<()> {
-        unimplemented!();
-        // let client = self.client.as_ref().unwrap();
-        // let bytes = client...;
-        // std::io::stdout().write_all(&bytes).unwrap();
-        // Ok(())
-    }
-
-    async fn list_certificates(&self, table_args: TableArgs) -> Result<()> {
+    async fn resource_rotate(&self, resource_type: &ResourceType, no_confirm: bool) -> Result<()> {
         let client = self.client.as_ref().unwrap();
-        let certs = client
-            .list_certificates(self.ctx.project_id())
-            .await?
-            .certificates;
-
-        let table = get_certificates_table(certs.as_ref(), table_args.raw);
-        println!("{}", table);
 
-        Ok(())
-    }
-    async fn add_certificate(&self, domain: String) -> Result<()> {
-        let client = self.client.as_ref().unwrap();
-        let cert = client
-            .add_certificate(self.ctx.project_id(), domain.clone())
+        if !no_confirm {
+            println!(
+                "{}",
+                formatdoc!(
+                    "
+                WARNING:
+                    Are you sure you want to rotate the credentials of this project's {}?
+                    Connections using the old password stop working, and the service is redeployed.",
+                    resource_type
+                )
+                .bold()
+                .red()
+            );
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Are you sure?")
+                .default(false)
+                .interact()
+                .unwrap()
+            {
+                return Ok(());
+            }
+        }
+
+        let pid = self.ctx.project_id();
+        let resource = client
+            .rotate_service_resource_credentials(pid, resource_type)
+            .await?;
+        println!(
+            "{}",
+            get_resource_tables(&[resource], &[], pid, false, false)
+        );
+        println!("Use `shuttle deployment status` to follow the redeploy.");
+
+        Ok(())
+    }
+
+    async fn resources_export(&self, output: PathBuf) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let bundle = client
+            .export_service_resources(self.ctx.project_id())
+            .await?;
+        let json = serde_json::to_string_pretty(&bundle)?;
+
+        std::fs::write(&output, json)
+            .with_context(|| format!("Failed to write to {}", output.display()))?;
+        println!(
+            "Exported {} resources to {}",
+            bundle.resources.len(),
+            output.display()
+        );
+        println!(
+            "{}",
+            "The export contains secrets such as database passwords. Keep it safe.".yellow()
+        );
+
+        Ok(())
+    }
+
+    async fn resources_import(&self, file: PathBuf, no_confirm: bool) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let bundle: ResourceExportBundle = serde_json::from_str(
+            &read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?,
+        )
+        .context("Failed to parse the resource export")?;
+
+        if !no_confirm {
+            println!(
+                "{}",
+                formatdoc!(
+                    "
+                WARNING:
+                    Importing will overwrite these resources of this project with the ones from project {}:
+                        {}",
+                    bundle.project_id,
+                    bundle
+                        .resources
+                        .iter()
+                        .map(|r| r.r#type.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n        ")
+                )
+                .bold()
+                .red()
+            );
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Are you sure?")
+                .default(false)
+                .interact()
+                .unwrap()
+            {
+                return Ok(());
+            }
+        }
+
+        let res = client
+            .import_service_resources(self.ctx.project_id(), bundle)
+            .await?;
+        println!("Imported {} resources", res.resources.len());
+
+        Ok(())
+    }
+
+    async fn resource_dump(
+        &self,
+        resource_type: &ResourceType,
+        output: Option<PathBuf>,
+        timeout: Duration,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let mut res = client
+            .dump_service_resource(self.ctx.project_id(), resource_type, timeout)
+            .await?;
+
+        let Some(path) = output else {
+            let mut out = std::io::stdout();
+            while let Some(chunk) = res.chunk().await? {
+                out.write_all(&chunk)?;
+            }
+            out.flush()?;
+
+            return Ok(());
+        };
+
+        let mut file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let written: Result<()> = async {
+            while let Some(chunk) = res.chunk().await? {
+                file.write_all(&chunk)?;
+            }
+            file.flush()?;
+
+            Ok(())
+        }
+        .await;
+        if let Err(e) = written {
+            // Don't leave a partial dump behind that looks like a complete one
+            drop(file);
+            let _ = std::fs::remove_file(&path);
+
+            return Err(e.context(format!("Failed to write dump to {}", path.display())));
+        }
+
+        eprintln!("Wrote the dump of {resource_type} to {}", path.display());
+
+        Ok(())
+    }
+
+    async fn resource_restore(
+        &self,
+        resource_type: &ResourceType,
+        file: PathBuf,
+        timeout: Duration,
+        no_confirm: bool,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let dump = tokio::fs::File::open(&file)
+            .await
+            .with_context(|| format!("Failed to open dump {}", file.display()))?;
+
+        if !no_confirm {
+            println!(
+                "{}",
+                formatdoc!(
+                    "
+                WARNING:
+                    Are you sure you want to restore this project's {} from {}?
+                    Data that is not in the dump can be lost.",
+                    resource_type,
+                    file.display()
+                )
+                .bold()
+                .red()
+            );
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Are you sure?")
+                .default(false)
+                .interact()
+                .unwrap()
+            {
+                return Ok(());
+            }
+        }
+
+        let msg = client
+            .restore_service_resource(self.ctx.project_id(), resource_type, dump, timeout)
+            .await?;
+        println!("{msg}");
+
+        Ok(())
+    }
+
+    async fn list_certificates(&self, table_args: TableArgs) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let certs = client
+            .list_certificates(self.ctx.project_id())
+            .await?
+            .certificates;
+
+        let table = get_certificates_table(certs.as_ref(), table_args.raw);
+        println!("{}", table);
+
+        Ok(())
+    }
+    async fn add_certificate(&self, domain: String) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+
+        // issuing a certificate fails if the DNS records are wrong, so check them first.
+        // The check is not available everywhere, so go ahead with the certificate if it fails.
+        match client
+            .verify_domain(self.ctx.project_id(), domain.clone())
+            .await
+        {
+            Ok(verification) if !verification.ready => {
+                eprint!("{}", verification.to_string_colored());
+                bail!("The DNS records of {domain} are not ready for a certificate");
+            }
+            Ok(_) => (),
+            Err(e) => debug!(error = %e, "failed to verify domain"),
+        }
+
+        let cert = client
+            .add_certificate(self.ctx.project_id(), domain.clone())
             .await?;
 
         println!("Added certificate for {}", cert.subject);
 
         Ok(())
     }
+    async fn verify_domain(&self, domain: String, wait: bool) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+
+        let verification = if wait {
+            wait_with_spinner(5000, |_, pb| {
+                let domain = domain.clone();
+                async move {
+                    let verification = client.verify_domain(pid, domain).await?;
+                    if verification.ready {
+                        return Ok(Some(move || verification));
+                    }
+                    let wrong = verification.records.iter().filter(|r| !r.ok).count();
+                    pb.set_message(format!(
+                        "Waiting for {wrong} DNS record(s) of {} to be correct",
+                        verification.subject
+                    ));
+                    Ok(None)
+                }
+            })
+            .await?
+        } else {
+            client.verify_domain(pid, domain).await?
+        };
+        print!("{}", verification.to_string_colored());
+
+        if !verification.ready {
+            bail!(
+                "The DNS records of {} are not ready for a certificate",
+                verification.subject
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn update_certificate(
+        &self,
+        domain: String,
+        hsts: Option<bool>,
+        http_paths: Option<Vec<String>>,
+    ) -> Result<()> {
+        if hsts.is_none() && http_paths.is_none() {
+            bail!("Nothing to update. Use --hsts, --http-path or --no-http-paths.");
+        }
+        let client = self.client.as_ref().unwrap();
+        let cert = client
+            .update_certificate(
+                self.ctx.project_id(),
+                UpdateCertificateRequest {
+                    subject: domain,
+                    hsts,
+                    http_paths,
+                },
+            )
+            .await?;
+
+        println!("Updated settings for {}", cert.subject);
+        println!("  HSTS: {}", if cert.hsts { "on" } else { "off" });
+        if cert.http_paths.is_empty() {
+            println!("  All HTTP requests are redirected to HTTPS");
+        } else {
+            println!("  Served over HTTP: {}", cert.http_paths.join(", "));
+        }
+
+        Ok(())
+    }
     async fn delete_certificate(&self, domain: String, no_confirm: bool) -> Result<()> {
         let client = self.client.as_ref().unwrap();
 
@@ -1124,6 +1873,106 @@ impl Shuttle {
         Ok(())
     }
 
+    async fn secrets_list(&self, table_args: TableArgs) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+        let secrets = client.get_secrets(pid).await?;
+        let table = get_secrets_table(&[secrets], pid, table_args.raw);
+
+        if table.is_empty() {
+            println!("No secrets are set for this project");
+        } else {
+            println!("{table}");
+        }
+
+        Ok(())
+    }
+
+    async fn secrets_set(&self, key: String, value: String) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        client
+            .update_secrets(
+                self.ctx.project_id(),
+                SecretsUpdateRequest {
+                    set: HashMap::from([(key.clone(), value)]),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        println!("Secret '{key}' was set");
+        Shuttle::print_secrets_redeploy_hint();
+
+        Ok(())
+    }
+
+    async fn secrets_unset(&self, key: String) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        client
+            .update_secrets(
+                self.ctx.project_id(),
+                SecretsUpdateRequest {
+                    unset: vec![key.clone()],
+                    ..Default::default()
+                },
+            )
+            .await?;
+        println!("Secret '{key}' was removed");
+        Shuttle::print_secrets_redeploy_hint();
+
+        Ok(())
+    }
+
+    fn print_secrets_redeploy_hint() {
+        println!(
+            "{}",
+            formatdoc! {"
+                Note:
+                    Secrets are loaded when a deployment starts.
+                    Run `shuttle deployment redeploy` to apply the change to the running deployment."
+            }
+            .yellow(),
+        );
+    }
+
+    async fn secrets_pull(&self, output: Option<PathBuf>, no_confirm: bool) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let output = output.unwrap_or_else(|| self.ctx.working_directory().join("Secrets.toml"));
+
+        if output.exists() && !no_confirm {
+            println!(
+                "{}",
+                formatdoc!(
+                    "
+                WARNING:
+                    {} already exists and will be overwritten.",
+                    output.display()
+                )
+                .bold()
+                .red()
+            );
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Are you sure?")
+                .default(false)
+                .interact()
+                .unwrap()
+            {
+                return Ok(());
+            }
+        }
+
+        let secrets = client.get_secrets(self.ctx.project_id()).await?;
+        let secrets: BTreeMap<String, String> =
+            serde_json::from_value::<SecretStore>(secrets.output)
+                .context("failed to parse secrets")?
+                .into_iter()
+                .collect();
+        std::fs::write(&output, toml::to_string(&secrets)?)
+            .with_context(|| format!("failed to write secrets to {}", output.display()))?;
+        println!("Wrote {} secrets to {}", secrets.len(), output.display());
+
+        Ok(())
+    }
+
     fn get_secrets(
         args: &SecretsArgs,
         workspace_root: &Path,
@@ -1157,6 +2006,29 @@ impl Shuttle {
         })
     }
 
+    /// Merge the secrets in `Secrets.<env>.toml` over the given secrets
+    fn overlay_secrets(
+        secrets: Option<HashMap<String, String>>,
+        workspace_root: &Path,
+        env: &str,
+    ) -> Result<HashMap<String, String>> {
+        let overlay_file = workspace_root.join(format!("Secrets.{env}.toml"));
+        let overlay_str = read_to_string(&overlay_file).with_context(|| {
+            format!(
+                "Secrets file for environment '{env}' not found: {}",
+                overlay_file.display()
+            )
+        })?;
+        let overlay = toml::from_str::<HashMap<String, String>>(&overlay_str)
+            .with_context(|| format!("failed to parse {}", overlay_file.display()))?;
+        trace!(keys = ?overlay.keys(), "overlaid secrets");
+
+        let mut secrets = secrets.unwrap_or_default();
+        secrets.extend(overlay);
+
+        Ok(secrets)
+    }
+
     async fn pre_local_run(&self, run_args: &RunArgs) -> Result<Vec<BuiltService>> {
         trace!("starting a local run with args: {run_args:?}");
 
@@ -1176,7 +2048,16 @@ impl Shuttle {
             working_directory.display()
         );
 
-        build_workspace(working_directory, run_args.release, tx, false).await
+        if run_args.docker {
+            // Docker Desktop on other systems can't share the host network with containers
+            if !cfg!(target_os = "linux") {
+                bail!("`--docker` is only supported on Linux, since it runs services on the host network");
+            }
+            build_workspace_in_docker(working_directory, run_args.release, tx, DOCKER_RUN_IMAGE)
+                .await
+        } else {
+            build_workspace(working_directory, run_args.release, tx, false).await
+        }
     }
 
     fn find_available_port(run_args: &mut RunArgs) {
@@ -1200,7 +2081,7 @@ impl Shuttle {
     async fn local_run(&self, mut run_args: RunArgs, debug: bool) -> Result<()> {
         let project_name = self.ctx.project_name().to_owned();
         let working_directory = self.ctx.working_directory();
-        let services = self.pre_local_run(&run_args).await?;
+        let mut services = self.pre_local_run(&run_args).await?;
         let service = services
             .first()
             .expect("at least one shuttle service")
@@ -1211,11 +2092,13 @@ impl Shuttle {
         let secrets =
             Shuttle::get_secrets(&run_args.secret_args, working_directory)?.unwrap_or_default();
         Shuttle::find_available_port(&mut run_args);
-        if let Some(warning) = check_and_warn_runtime_version(&service.executable_path).await? {
-            eprint!("{}", warning);
+        // The executable built in Docker might not run on this host
+        if !run_args.docker {
+            if let Some(warning) = check_and_warn_runtime_version(&service.executable_path).await? {
+                eprint!("{}", warning);
+            }
         }
 
-        let runtime_executable = service.executable_path.clone();
         let api_port = portpicker::pick_unused_port()
             .expect("failed to find available port for local provisioner server");
         let api_addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), api_port);
@@ -1225,50 +2108,249 @@ impl Shuttle {
             Ipv4Addr::LOCALHOST
         };
 
+        let seeds = run_args
+            .seed
+            .iter()
+            .map(|(engine, path)| {
+                let sql = std::fs::read(path)
+                    .with_context(|| format!("Failed to read seed file {}", path.display()))?;
+                Ok((engine.clone(), sql))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
         let state = Arc::new(ProvApiState {
             project_name: project_name.clone(),
             secrets,
+            seeds,
+            reseed: run_args.reseed,
         });
         tokio::spawn(async move { ProvisionerServer::run(state, &api_addr).await });
 
-        println!(
-            "\n    {} {} on http://{}:{}\n",
-            "Starting".bold().green(),
-            service.package_name,
-            ip,
-            run_args.port,
-        );
-
         let mut envs = vec![
             ("SHUTTLE_BETA", "true".to_owned()),
             ("SHUTTLE_PROJECT_ID", "proj_LOCAL".to_owned()),
-            ("SHUTTLE_PROJECT_NAME", project_name),
+            ("SHUTTLE_PROJECT_NAME", project_name.clone()),
             ("SHUTTLE_ENV", Environment::Local.to_string()),
             ("SHUTTLE_RUNTIME_IP", ip.to_string()),
-            ("SHUTTLE_RUNTIME_PORT", run_args.port.to_string()),
             ("SHUTTLE_API", format!("http://127.0.0.1:{}", api_port)),
         ];
+        // Give the service the same git info as it would get in a deployment
+        if let Ok(repo) = Repository::discover(working_directory) {
+            let build_meta = git_build_meta(&repo, is_dirty(&repo).is_err());
+            envs.extend(
+                [
+                    ("SHUTTLE_GIT_COMMIT_ID", build_meta.git_commit_id),
+                    ("SHUTTLE_GIT_COMMIT_MSG", build_meta.git_commit_msg),
+                    ("SHUTTLE_GIT_BRANCH", build_meta.git_branch),
+                    (
+                        "SHUTTLE_GIT_DIRTY",
+                        build_meta.git_dirty.map(|d| d.to_string()),
+                    ),
+                ]
+                .into_iter()
+                .filter_map(|(k, v)| v.map(|v| (k, v))),
+            );
+        }
         // Use a nice debugging tracing level if user does not provide their own
         if debug && std::env::var("RUST_LOG").is_err() {
             envs.push(("RUST_LOG", "info,shuttle=trace,reqwest=debug".to_owned()));
         }
 
+        // Only buffer one change: anything that comes in while a rebuild is pending is covered by it
+        let (watch_tx, mut watch_rx) = tokio::sync::mpsc::channel::<()>(1);
+        let _watcher = if run_args.watch {
+            Some(watch_workspace(working_directory, watch_tx)?)
+        } else {
+            drop(watch_tx);
+            None
+        };
+
+        for (name, _) in &run_args.port_map {
+            if !services.iter().any(|s| &s.package_name == name) {
+                eprintln!(
+                    "{}",
+                    format!("No Shuttle service named '{name}' in the workspace, ignoring its port mapping.")
+                        .yellow()
+                );
+            }
+        }
+
+        loop {
+            let ports = Shuttle::service_ports(&services, &run_args)?;
+
+            println!();
+            for (service, port) in services.iter().zip(&ports) {
+                println!(
+                    "    {} {} on http://{}:{}",
+                    "Starting".bold().green(),
+                    service.package_name,
+                    ip,
+                    port,
+                );
+            }
+            println!();
+
+            let containers = services
+                .iter()
+                .map(|s| format!("shuttle_{project_name}_{}_run", s.package_name))
+                .collect::<Vec<_>>();
+            let mut runtimes = Vec::with_capacity(services.len());
+            for ((service, port), container) in services.iter().zip(&ports).zip(&containers) {
+                let mut envs = envs.clone();
+                envs.push(("SHUTTLE_RUNTIME_PORT", port.to_string()));
+                let container = if run_args.docker {
+                    // A previous run might not have been cleaned up
+                    remove_docker_container(container).await?;
+                    Some(container.as_str())
+                } else {
+                    None
+                };
+                runtimes.push(Shuttle::spawn_runtime(
+                    service,
+                    &envs,
+                    run_args.raw,
+                    container,
+                )?);
+            }
+
+            // `None` means that a change was detected
+            let exited = tokio::select! {
+                (exit_result, idx, _) = futures::future::select_all(
+                    runtimes.iter_mut().map(|r| Box::pin(r.wait()))
+                ) => Some(Some((idx, exit_result))),
+                _ = Shuttle::wait_for_shutdown_signal() => None,
+                Some(()) = watch_rx.recv() => Some(None),
+            };
+            for runtime in runtimes.iter_mut() {
+                // fails for the runtime that already exited, which is fine
+                let _ = runtime.kill().await;
+            }
+            if run_args.docker {
+                for container in &containers {
+                    remove_docker_container(container).await?;
+                }
+            }
+            let Some(exited) = exited else {
+                return Ok(());
+            };
+
+            if let Some((idx, exit_result)) = exited {
+                let exit_status = match exit_result {
+                    Ok(exit_status) => exit_status,
+                    Err(e) => bail!("Failed to wait for runtime process to exit: {e}"),
+                };
+                let name = &services[idx].package_name;
+                if !run_args.watch {
+                    bail!(
+                        "Runtime process of {name} exited with code {}",
+                        exit_status.code().unwrap_or_default()
+                    );
+                }
+                eprintln!(
+                    "Runtime process of {name} exited with code {}. Waiting for changes...",
+                    exit_status.code().unwrap_or_default()
+                );
+                tokio::select! {
+                    Some(()) = watch_rx.recv() => {}
+                    _ = Shuttle::wait_for_shutdown_signal() => return Ok(()),
+                }
+            }
+
+            // A change was detected. Keep trying to rebuild on every new change until it succeeds.
+            loop {
+                // Let a burst of file events (such as an editor saving several files) settle
+                sleep(Duration::from_millis(300)).await;
+                while watch_rx.try_recv().is_ok() {}
+
+                println!("\n{}", "Change detected, rebuilding...".bold());
+                match self.pre_local_run(&run_args).await {
+                    Ok(new_services) => {
+                        services = new_services;
+                        break;
+                    }
+                    Err(e) => {
+                        eprintln!("{}", format!("Build failed: {e:?}").red());
+                        eprintln!("Waiting for changes...");
+                        tokio::select! {
+                            Some(()) = watch_rx.recv() => {}
+                            _ = Shuttle::wait_for_shutdown_signal() => return Ok(()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pick the port for each service: mapped services get their own port,
+    /// the rest count up from the (available) port in the run args
+    fn service_ports(services: &[BuiltService], run_args: &RunArgs) -> Result<Vec<u16>> {
+        let mut next_port = run_args.port;
+        let mut ports = Vec::with_capacity(services.len());
+        for service in services {
+            let mapped = run_args
+                .port_map
+                .iter()
+                .find(|(name, _)| name == &service.package_name);
+            let port = match mapped {
+                Some((name, port)) => {
+                    if !portpicker::is_free_tcp(*port) {
+                        bail!("Port {port} for {name} is already in use");
+                    }
+                    *port
+                }
+                None => {
+                    // Skip ports that are mapped to other services or taken by other processes
+                    let port = (next_port..=u16::MAX)
+                        .find(|port| {
+                            !run_args.port_map.iter().any(|(_, p)| p == port)
+                                && !ports.contains(port)
+                                && portpicker::is_free_tcp(*port)
+                        })
+                        .with_context(|| {
+                            format!("No free port for {} from {next_port}", service.package_name)
+                        })?;
+                    next_port = port.saturating_add(1);
+                    port
+                }
+            };
+            ports.push(port);
+        }
+
+        Ok(ports)
+    }
+
+    /// Spawn the runtime process of a built service and forward its output to stdout.
+    /// With a container name, the runtime is run in a Docker container of that name.
+    fn spawn_runtime(
+        service: &BuiltService,
+        envs: &[(&str, String)],
+        raw: bool,
+        container: Option<&str>,
+    ) -> Result<tokio::process::Child> {
+        let runtime_executable = service.executable_path.clone();
         info!(
             path = %runtime_executable.display(),
             "Spawning runtime process",
         );
-        let mut runtime = tokio::process::Command::new(
-            dunce::canonicalize(runtime_executable).context("canonicalize path of executable")?,
-        )
-        .current_dir(&service.workspace_path)
-        .envs(envs)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .kill_on_drop(true)
-        .spawn()
-        .context("spawning runtime process")?;
-
-        let raw = run_args.raw;
+        let mut command = match container {
+            Some(name) => Shuttle::docker_runtime_command(service, envs, name)?,
+            None => {
+                let mut command = tokio::process::Command::new(
+                    dunce::canonicalize(runtime_executable)
+                        .context("canonicalize path of executable")?,
+                );
+                command
+                    .current_dir(&service.workspace_path)
+                    .envs(envs.iter().map(|(k, v)| (k, v)));
+                command
+            }
+        };
+        let mut runtime = command
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .context("spawning runtime process")?;
+
         let mut stdout_reader = BufReader::new(
             runtime
                 .stdout
@@ -1304,82 +2386,92 @@ impl Shuttle {
             }
         });
 
-        #[cfg(target_family = "unix")]
-        let exit_result = {
-            let mut sigterm_notif =
-                tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
-                    .expect("Can not get the SIGTERM signal receptor");
-            let mut sigint_notif =
-                tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())
-                    .expect("Can not get the SIGINT signal receptor");
-            tokio::select! {
-                exit_result = runtime.wait() => {
-                    Some(exit_result)
-                }
-                _ = sigterm_notif.recv() => {
-                    eprintln!("Received SIGTERM. Killing the runtime...");
-                    None
-                },
-                _ = sigint_notif.recv() => {
-                    eprintln!("Received SIGINT. Killing the runtime...");
-                    None
-                }
-            }
-        };
-        #[cfg(target_family = "windows")]
-        let exit_result = {
-            let mut ctrl_break_notif = tokio::signal::windows::ctrl_break()
-                .expect("Can not get the CtrlBreak signal receptor");
-            let mut ctrl_c_notif =
-                tokio::signal::windows::ctrl_c().expect("Can not get the CtrlC signal receptor");
-            let mut ctrl_close_notif = tokio::signal::windows::ctrl_close()
-                .expect("Can not get the CtrlClose signal receptor");
-            let mut ctrl_logoff_notif = tokio::signal::windows::ctrl_logoff()
-                .expect("Can not get the CtrlLogoff signal receptor");
-            let mut ctrl_shutdown_notif = tokio::signal::windows::ctrl_shutdown()
-                .expect("Can not get the CtrlShutdown signal receptor");
-            tokio::select! {
-                exit_result = runtime.wait() => {
-                    Some(exit_result)
-                }
-                _ = ctrl_break_notif.recv() => {
-                    eprintln!("Received ctrl-break.");
-                    None
-                },
-                _ = ctrl_c_notif.recv() => {
-                    eprintln!("Received ctrl-c.");
-                    None
-                },
-                _ = ctrl_close_notif.recv() => {
-                    eprintln!("Received ctrl-close.");
-                    None
-                },
-                _ = ctrl_logoff_notif.recv() => {
-                    eprintln!("Received ctrl-logoff.");
-                    None
-                },
-                _ = ctrl_shutdown_notif.recv() => {
-                    eprintln!("Received ctrl-shutdown.");
-                    None
-                }
-            }
-        };
-        match exit_result {
-            Some(Ok(exit_status)) => {
-                bail!(
-                    "Runtime process exited with code {}",
-                    exit_status.code().unwrap_or_default()
-                );
-            }
-            Some(Err(e)) => {
-                bail!("Failed to wait for runtime process to exit: {e}");
-            }
-            None => {
-                runtime.kill().await?;
+        Ok(runtime)
+    }
+
+    /// Command to run the executable of a service built by [`build_workspace_in_docker`] in a container.
+    /// The host network is used so that the service can reach the local provisioner and databases.
+    fn docker_runtime_command(
+        service: &BuiltService,
+        envs: &[(&str, String)],
+        container: &str,
+    ) -> Result<tokio::process::Command> {
+        let mut command = tokio::process::Command::new("docker");
+        command
+            .args([
+                "run",
+                "--rm",
+                "--init",
+                "--network",
+                "host",
+                "--name",
+                container,
+            ])
+            .args(docker_user_args(&service.workspace_path)?)
+            // Same paths as in the build container
+            .arg("--workdir")
+            .arg(&service.workspace_path)
+            .arg("--volume")
+            .arg(format!(
+                "{}:{}",
+                service.workspace_path.display(),
+                service.workspace_path.display()
+            ));
+        for (key, value) in envs {
+            command.arg("--env").arg(format!("{key}={value}"));
+        }
+        command.arg(DOCKER_RUN_IMAGE).arg(&service.executable_path);
+
+        Ok(command)
+    }
+
+    #[cfg(target_family = "unix")]
+    async fn wait_for_shutdown_signal() {
+        let mut sigterm_notif =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                .expect("Can not get the SIGTERM signal receptor");
+        let mut sigint_notif =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())
+                .expect("Can not get the SIGINT signal receptor");
+        tokio::select! {
+            _ = sigterm_notif.recv() => {
+                eprintln!("Received SIGTERM. Killing the runtime...");
+            },
+            _ = sigint_notif.recv() => {
+                eprintln!("Received SIGINT. Killing the runtime...");
             }
         }
+    }
 
-        Ok(())
+    #[cfg(target_family = "windows")]
+    async fn wait_for_shutdown_signal() {
+        let mut ctrl_break_notif = tokio::signal::windows::ctrl_break()
+            .expect("Can not get the CtrlBreak signal receptor");
+        let mut ctrl_c_notif =
+            tokio::signal::windows::ctrl_c().expect("Can not get the CtrlC signal receptor");
+        let mut ctrl_close_notif = tokio::signal::windows::ctrl_close()
+            .expect("Can not get the CtrlClose signal receptor");
+        let mut ctrl_logoff_notif = tokio::signal::windows::ctrl_logoff()
+            .expect("Can not get the CtrlLogoff signal receptor");
+        let mut ctrl_shutdown_notif = tokio::signal::windows::ctrl_shutdown()
+            .expect("Can not get the CtrlShutdown signal receptor");
+        tokio::select! {
+            _ = ctrl_break_notif.recv() => {
+                eprintln!("Received ctrl-break.");
+            },
+            _ = ctrl_c_notif.recv() => {
+                eprintln!("Received ctrl-c.");
+            },
+            _ = ctrl_close_notif.recv() => {
+                eprintln!("Received ctrl-close.");
+            },
+            _ = ctrl_logoff_notif.recv() => {
+                eprintln!("Received ctrl-logoff.");
+            },
+            _ = ctrl_shutdown_notif.recv() => {
+                eprintln!("Received ctrl-shutdown.");
+            }
+        }
     }
 
     async fn deploy(&mut self, args: DeployArgs) -> Result<()> {
@@ -1387,12 +2479,84 @@ impl Shuttle {
         let working_directory = self.ctx.working_directory();
         let manifest_path = working_directory.join("Cargo.toml");
 
-        let secrets = Shuttle::get_secrets(&args.secret_args, working_directory)?;
+        if args.dry_run {
+            // Pack the same secrets as a deployment would
+            let merged_secrets = match args.secrets_env.as_deref() {
+                Some(env) => Some(Shuttle::overlay_secrets(
+                    Shuttle::get_secrets(&args.secret_args, working_directory)?,
+                    working_directory,
+                    env,
+                )?),
+                None => None,
+            };
+            eprintln!("Packing files...");
+            let archive = self.make_archive(args.secret_args.secrets, merged_secrets.as_ref())?;
+            print!("{}", archive_listing(&archive)?);
+
+            return Ok(());
+        }
+
+        // Shuttle.toml resources are declared for the linked project.
+        // Writing an archive does not deploy, so it skips the check and works offline.
+        if let Some(declared) = self
+            .ctx
+            .declared_resources()
+            .filter(|_| !args.all && args.output_archive.is_none())
+        {
+            let pid = self.ctx.project_id();
+            let existing = client
+                .get_service_resources(pid)
+                .await?
+                .resources
+                .into_iter()
+                .map(|r| r.r#type)
+                .collect::<Vec<_>>();
+            let new = missing_declared_resources(declared, &existing)?;
+            let new_rds = new.iter().filter(|t| is_rds(t)).count();
+            if new_rds > 0 {
+                // RDS subscriptions are shared by all projects of the account
+                let mut account_rds = 0;
+                for project in client.get_projects_list().await?.projects {
+                    account_rds += client
+                        .get_service_resources(&project.id)
+                        .await?
+                        .resources
+                        .iter()
+                        .filter(|r| is_rds(&r.r#type))
+                        .count();
+                }
+                let subscriptions = client.get_current_user().await?.subscriptions;
+                check_rds_subscriptions(account_rds + new_rds, &subscriptions)?;
+            }
+            for resource_type in new {
+                eprintln!("{resource_type} will be provisioned by this deployment");
+            }
+        }
+
+        let mut secrets = Shuttle::get_secrets(&args.secret_args, working_directory)?;
+        // With `--all`, secrets are loaded for each service below
+        if let Some(env) = args.secrets_env.as_deref().filter(|_| !args.all) {
+            secrets = Some(Shuttle::overlay_secrets(secrets, working_directory, env)?);
+        }
+        let health_check_path = self.ctx.health_check_path().cloned();
+        let env = (!args.env.is_empty()).then(|| args.env.into_iter().collect::<HashMap<_, _>>());
 
         // Image deployment mode
         if let Some(image) = args.image {
+            if !image.contains('@') {
+                eprintln!(
+                    "{}",
+                    "Deploying an image by tag. Pin it by digest (image@sha256:...) to make sure this exact build is deployed."
+                        .yellow()
+                );
+            }
             let pid = self.ctx.project_id();
-            let deployment_req_image = DeploymentRequestImage { image, secrets };
+            let deployment_req_image = DeploymentRequestImage {
+                image,
+                secrets,
+                health_check_path,
+                env,
+            };
 
             let deployment = client
                 .deploy(pid, DeploymentRequest::Image(deployment_req_image))
@@ -1412,43 +2576,51 @@ impl Shuttle {
         // Build archive deployment mode
         let mut deployment_req = DeploymentRequestBuildArchive {
             secrets,
+            health_check_path,
+            env,
+            git_credentials: (!args.git_credential.is_empty())
+                .then(|| args.git_credential.into_iter().collect()),
             ..Default::default()
         };
         let mut build_meta = BuildMeta::default();
-        let mut rust_build_args = BuildArgsRust::default();
 
         let metadata = async_cargo_metadata(manifest_path.as_path()).await?;
         let packages = find_shuttle_packages(&metadata)?;
-        // TODO: support overriding this
-        let package = packages
-            .first()
-            .expect("Expected at least one crate with shuttle-runtime in the workspace");
-        let package_name = package.name.to_owned();
-        rust_build_args.package_name = Some(package_name);
-
-        // activate shuttle feature if present
-        let (no_default_features, features) = if package.features.contains_key("shuttle") {
-            (true, Some(vec!["shuttle".to_owned()]))
-        } else {
-            (false, None)
+        let package = match args.package.as_deref() {
+            _ if args.all => None,
+            Some(name) => Some(packages.iter().find(|p| p.name == name).with_context(|| {
+                format!(
+                    "No Shuttle service named '{name}' found in the workspace. Available services: {}",
+                    packages
+                        .iter()
+                        .map(|p| p.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?),
+            None => {
+                let package = packages
+                    .first()
+                    .expect("Expected at least one crate with shuttle-runtime in the workspace");
+                if packages.len() > 1 {
+                    eprintln!(
+                        "{}",
+                        format!(
+                            "Found {} Shuttle services in the workspace, deploying '{}'. Use `--package` to select another one.",
+                            packages.len(),
+                            package.name
+                        )
+                        .yellow()
+                    );
+                }
+                Some(package)
+            }
         };
-        rust_build_args.no_default_features = no_default_features;
-        rust_build_args.features = features.map(|v| v.join(","));
-
-        rust_build_args.shuttle_runtime_version = package
-            .dependencies
-            .iter()
-            .find(|dependency| dependency.name == RUNTIME_NAME)
-            .expect("shuttle package to have runtime dependency")
-            .req
-            .comparators
-            .first()
-            // is "^0.X.0" when `shuttle-runtime = "0.X.0"` is in Cargo.toml
-            .and_then(|c| c.to_string().strip_prefix('^').map(ToOwned::to_owned));
-
-        // TODO: determine which (one) binary to build
-
-        deployment_req.build_args = Some(BuildArgs::Rust(rust_build_args));
+        if let Some(package) = package {
+            deployment_req.build_args = Some(BuildArgs::Rust(
+                self.rust_build_args(package, args.no_cache),
+            ));
+        }
 
         // TODO: have all of the above be configurable in CLI and Shuttle.toml
 
@@ -1460,31 +2632,21 @@ impl Shuttle {
             trace!(?repo_path, "found git repository");
 
             let dirty = is_dirty(&repo);
-            build_meta.git_dirty = Some(dirty.is_err());
 
             let check_dirty = self.ctx.deny_dirty().is_some_and(|d| d);
             if check_dirty && !args.allow_dirty && dirty.is_err() {
                 bail!(dirty.unwrap_err());
             }
 
-            if let Ok(head) = repo.head() {
-                // This is typically the name of the current branch
-                // It is "HEAD" when head detached, for example when a tag is checked out
-                build_meta.git_branch = head
-                    .shorthand()
-                    .map(|s| s.chars().take(GIT_STRINGS_MAX_LENGTH).collect());
-                if let Ok(commit) = head.peel_to_commit() {
-                    build_meta.git_commit_id = Some(commit.id().to_string());
-                    // Summary is None if error or invalid utf-8
-                    build_meta.git_commit_msg = commit
-                        .summary()
-                        .map(|s| s.chars().take(GIT_STRINGS_MAX_LENGTH).collect());
-                }
-            }
+            build_meta = git_build_meta(&repo, dirty.is_err());
         }
 
         eprintln!("Packing files...");
-        let archive = self.make_archive(args.secret_args.secrets.clone())?;
+        // the merged secrets replace Secrets.toml in the archive
+        let merged_secrets = (args.secrets_env.is_some() && !args.all)
+            .then_some(deployment_req.secrets.as_ref())
+            .flatten();
+        let archive = self.make_archive(args.secret_args.secrets.clone(), merged_secrets)?;
 
         if let Some(path) = args.output_archive {
             eprintln!("Writing archive to {}", path.display());
@@ -1493,6 +2655,32 @@ impl Shuttle {
             return Ok(());
         }
 
+        if args.all {
+            deployment_req.build_meta = Some(build_meta);
+            let targets = packages
+                .iter()
+                .map(|package| {
+                    let secrets = Shuttle::package_secrets(
+                        package,
+                        &args.secret_args,
+                        working_directory,
+                        args.secrets_env.as_deref(),
+                    )?;
+                    Ok((package, secrets))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return self
+                .deploy_all(
+                    targets,
+                    deployment_req,
+                    archive,
+                    args.no_cache,
+                    args.no_follow,
+                    args.yes,
+                )
+                .await;
+        }
+
         // TODO: upload secrets separately
 
         let pid = self.ctx.project_id();
@@ -1504,7 +2692,10 @@ impl Shuttle {
 
         eprintln!("Creating deployment...");
         let deployment = client
-            .deploy(pid, DeploymentRequest::BuildArchive(deployment_req))
+            .deploy(
+                pid,
+                DeploymentRequest::BuildArchive(Box::new(deployment_req)),
+            )
             .await?;
 
         if args.no_follow {
@@ -1518,6 +2709,193 @@ impl Shuttle {
         Ok(())
     }
 
+    /// Build arguments for deploying a package of the workspace
+    fn rust_build_args(&self, package: &Package, no_cache: bool) -> BuildArgsRust {
+        let mut rust_build_args = BuildArgsRust {
+            package_name: Some(package.name.to_owned()),
+            no_cache,
+            ..Default::default()
+        };
+
+        // activate shuttle feature if present
+        let (no_default_features, mut features) = if package.features.contains_key("shuttle") {
+            (true, Some(vec!["shuttle".to_owned()]))
+        } else {
+            (false, None)
+        };
+        // and any features from Shuttle.toml
+        if let Some(build_features) = self.ctx.build_features() {
+            features
+                .get_or_insert_with(Vec::new)
+                .extend(build_features.iter().cloned());
+        }
+        rust_build_args.no_default_features = no_default_features;
+        rust_build_args.features = features.map(|v| v.join(","));
+
+        rust_build_args.shuttle_runtime_version = package
+            .dependencies
+            .iter()
+            .find(|dependency| dependency.name == RUNTIME_NAME)
+            .expect("shuttle package to have runtime dependency")
+            .req
+            .comparators
+            .first()
+            // is "^0.X.0" when `shuttle-runtime = "0.X.0"` is in Cargo.toml
+            .and_then(|c| c.to_string().strip_prefix('^').map(ToOwned::to_owned));
+
+        // TODO: determine which (one) binary to build
+
+        rust_build_args
+    }
+
+    /// Secrets for deploying a package of the workspace with `deploy --all`.
+    /// The Secrets.toml (and Secrets.<ENV>.toml) in the package directory are used if they exist,
+    /// otherwise the ones in the workspace root.
+    fn package_secrets(
+        package: &Package,
+        secret_args: &SecretsArgs,
+        workspace_root: &Path,
+        secrets_env: Option<&str>,
+    ) -> Result<Option<HashMap<String, String>>> {
+        let package_dir = package
+            .manifest_path
+            .parent()
+            .expect("manifest to be in a directory")
+            .as_std_path();
+        let secrets_root =
+            if secret_args.secrets.is_none() && package_dir.join("Secrets.toml").is_file() {
+                package_dir
+            } else {
+                workspace_root
+            };
+
+        let mut secrets = Shuttle::get_secrets(secret_args, secrets_root)?;
+        if let Some(env) = secrets_env {
+            secrets = Some(Shuttle::overlay_secrets(secrets, secrets_root, env)?);
+        }
+
+        Ok(secrets)
+    }
+
+    /// Deploy all Shuttle services of the workspace concurrently, each to the project named after its package
+    async fn deploy_all(
+        &self,
+        packages: Vec<(&Package, Option<HashMap<String, String>>)>,
+        deployment_req: DeploymentRequestBuildArchive,
+        archive: Vec<u8>,
+        no_cache: bool,
+        no_follow: bool,
+        no_confirm: bool,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+
+        let projects = client.get_projects_list().await?.projects;
+        let missing = packages
+            .iter()
+            .map(|(package, _)| package.name.as_str())
+            .filter(|name| !projects.iter().any(|p| p.name == *name))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() && !no_confirm {
+            eprintln!(
+                "These services don't have a project yet: {}",
+                missing.join(", ")
+            );
+            if !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Create the projects?")
+                .default(false)
+                .interact()?
+            {
+                return Ok(());
+            }
+        }
+
+        let mut targets = Vec::new();
+        for (package, secrets) in packages {
+            let pid = match projects.iter().find(|p| p.name == package.name) {
+                Some(proj) => proj.id.clone(),
+                None => {
+                    let proj = client.create_project(&package.name).await?;
+                    eprintln!("Created project '{}' with id {}", proj.name, proj.id);
+                    proj.id
+                }
+            };
+            let mut req = deployment_req.clone();
+            req.build_args = Some(BuildArgs::Rust(self.rust_build_args(package, no_cache)));
+            req.secrets = secrets;
+            targets.push((package.name.clone(), pid, req));
+        }
+
+        eprintln!("Deploying {} services...", targets.len());
+        let progress = MultiProgress::new();
+        let results = futures::future::join_all(targets.into_iter().map(|(name, pid, mut req)| {
+            let pb = progress.add(create_spinner());
+            let archive = archive.clone();
+            async move {
+                pb.set_message(format!("{name}: uploading code"));
+                let res = async {
+                    let arch = client.upload_archive(&pid, archive).await?;
+                    req.archive_version_id = arch.archive_version_id;
+                    let deployment = client
+                        .deploy(&pid, DeploymentRequest::BuildArchive(Box::new(req)))
+                        .await?;
+                    if no_follow {
+                        return anyhow::Ok(deployment);
+                    }
+                    loop {
+                        let deployment = client.get_deployment(&pid, &deployment.id).await?;
+                        pb.set_message(format!(
+                            "{name}: {}",
+                            deployment.to_string_summary_colored()
+                        ));
+                        match deployment.state {
+                            DeploymentState::Pending
+                            | DeploymentState::Building
+                            | DeploymentState::InProgress => {
+                                sleep(Duration::from_millis(2000)).await
+                            }
+                            _ => return anyhow::Ok(deployment),
+                        }
+                    }
+                }
+                .await;
+                pb.finish_and_clear();
+
+                (name, pid, res)
+            }
+        }))
+        .await;
+
+        let total = results.len();
+        let mut failed = 0;
+        for (name, pid, res) in results {
+            match res {
+                Ok(deployment) if no_follow => {
+                    println!("{name}: {}", deployment.to_string_summary_colored());
+                }
+                Ok(deployment) if deployment.state == DeploymentState::Running => {
+                    println!("{name}: {}", deployment.to_string_summary_colored());
+                }
+                Ok(deployment) => {
+                    failed += 1;
+                    println!("{name}: {}", deployment.to_string_summary_colored());
+                    println!(
+                        "  See the logs with `cargo shuttle logs --name {pid} {}`",
+                        deployment.id
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("{name}: {}", format!("{e:#}").red());
+                }
+            }
+        }
+        if failed > 0 {
+            bail!("{failed} of {total} deployments failed");
+        }
+
+        Ok(())
+    }
+
     /// Returns true if the deployment failed
     async fn track_deployment_status(&self, pid: &str, id: &str) -> Result<bool> {
         let client = self.client.as_ref().unwrap();
@@ -1555,13 +2933,46 @@ impl Shuttle {
     ) -> Result<()> {
         let client = self.client.as_ref().unwrap();
         if self.track_deployment_status(proj_id, depl_id).await? {
-            for log in client.get_deployment_logs(proj_id, depl_id).await?.logs {
+            let logs = client.get_deployment_logs(proj_id, depl_id).await?.logs;
+            for log in &logs {
                 if raw {
                     println!("{}", log.line);
                 } else {
                     println!("{log}");
                 }
             }
+            if self.ctx.crash_reports() {
+                let lines = logs.into_iter().map(|l| l.line).collect::<Vec<_>>();
+                match crate::report::write_report(
+                    &format!("Deployment {depl_id} failed"),
+                    "",
+                    &lines,
+                ) {
+                    Ok(path) => eprintln!(
+                        "A crash report was saved to {}. Use `cargo shuttle feedback --attach-report` if this looks like a Shuttle issue.",
+                        path.display()
+                    ),
+                    Err(e) => debug!(error = %e, "failed to write crash report"),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn telemetry(&mut self, cmd: TelemetryCommand) -> Result<()> {
+        match cmd {
+            TelemetryCommand::Status => {}
+            TelemetryCommand::Enable => self.ctx.set_crash_reports(true)?,
+            TelemetryCommand::Disable => self.ctx.set_crash_reports(false)?,
+        }
+        if self.ctx.crash_reports() {
+            println!(
+                "Crash reports are enabled. The latest report is saved to {}",
+                crate::report::report_path().display()
+            );
+        } else {
+            println!("Crash reports are disabled.");
         }
 
         Ok(())
@@ -1590,15 +3001,37 @@ impl Shuttle {
             .await?;
 
         println!("Renamed project {} to {}", project.id, project.name);
+        for uri in &project.uris {
+            println!("  - {uri}");
+        }
 
         Ok(())
     }
 
-    async fn projects_list(&self, table_args: TableArgs) -> Result<()> {
+    async fn projects_list(
+        &self,
+        states: Vec<DeploymentState>,
+        sort: Option<ProjectListSort>,
+        table_args: TableArgs,
+    ) -> Result<()> {
         let client = self.client.as_ref().unwrap();
 
-        let projects_table =
-            get_projects_table(&client.get_projects_list().await?.projects, table_args.raw);
+        let mut projects = client.get_projects_list().await?.projects;
+        if !states.is_empty() {
+            projects.retain(|p| {
+                p.deployment_state
+                    .as_ref()
+                    .is_some_and(|s| states.contains(s))
+            });
+        }
+        match sort {
+            Some(ProjectListSort::Name) => projects.sort_by(|a, b| a.name.cmp(&b.name)),
+            Some(ProjectListSort::Created) => {
+                projects.sort_by_key(|p| std::cmp::Reverse(p.created_at))
+            }
+            None => (),
+        }
+        let projects_table = get_projects_table(&projects, table_args.raw);
 
         println!("{}", "Personal Projects".bold());
         println!("{projects_table}\n");
@@ -1606,11 +3039,130 @@ impl Shuttle {
         Ok(())
     }
 
-    async fn project_status(&self) -> Result<()> {
+    async fn project_status(&self, follow: bool) -> Result<()> {
         let client = self.client.as_ref().unwrap();
-        let project = client.get_project(self.ctx.project_id()).await?;
+        let pid = self.ctx.project_id();
+        let project = client.get_project(pid).await?;
         print!("{}", project.to_string_colored());
 
+        if !follow {
+            return Ok(());
+        }
+        println!();
+        println!("{}", "Following state changes (Ctrl+C to stop):".bold());
+        let mut rx = client.get_project_events_ws(pid).await?;
+        while let Some(text) = read_ws_until_text(&mut rx).await? {
+            match serde_json::from_str::<ProjectEvent>(&text) {
+                Ok(event) => println!("{}", event.to_string_colored()),
+                Err(e) => debug!(error = %e, "failed to parse project event"),
+            }
+        }
+        println!("The event stream was closed.");
+
+        Ok(())
+    }
+
+    async fn project_stats(&self) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let stats = client.get_project_stats(self.ctx.project_id()).await?;
+        print!("{}", stats.to_string_colored());
+
+        Ok(())
+    }
+
+    async fn project_usage(&self) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let usage = client.get_project_usage(self.ctx.project_id()).await?;
+        print!("{}", usage.to_string_colored());
+
+        Ok(())
+    }
+
+    async fn project_protect(
+        &self,
+        allowed_ips: Vec<String>,
+        basic_auth_user: Option<String>,
+        remove: bool,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+
+        let rules = if remove {
+            client
+                .set_project_access_rules(pid, ProjectAccessRulesRequest::default())
+                .await?
+        } else if !allowed_ips.is_empty() || basic_auth_user.is_some() {
+            // Keep the kind of rule that was not given
+            let current = client.get_project_access_rules(pid).await?;
+            let basic_auth = match basic_auth_user {
+                Some(username) => {
+                    let password = Password::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!("Password for {username}"))
+                        .with_confirmation("Repeat password", "Passwords don't match")
+                        .validate_with(|input: &String| {
+                            if input.is_empty() {
+                                return Err("Empty password was provided");
+                            }
+                            Ok(())
+                        })
+                        .interact()?;
+                    Some(BasicAuthCredentials { username, password })
+                }
+                None => None,
+            };
+            client
+                .set_project_access_rules(
+                    pid,
+                    ProjectAccessRulesRequest {
+                        allowed_ips: if allowed_ips.is_empty() {
+                            current.allowed_ips
+                        } else {
+                            allowed_ips
+                        },
+                        keep_basic_auth: basic_auth.is_none()
+                            && current.basic_auth_username.is_some(),
+                        basic_auth,
+                    },
+                )
+                .await?
+        } else {
+            client.get_project_access_rules(pid).await?
+        };
+        print!("{}", rules.to_string_colored());
+
+        Ok(())
+    }
+
+    async fn project_schedule(
+        &self,
+        stop: Option<String>,
+        start: Option<String>,
+        remove: bool,
+    ) -> Result<()> {
+        let client = self.client.as_ref().unwrap();
+        let pid = self.ctx.project_id();
+
+        let schedule = if remove {
+            client
+                .set_project_schedule(pid, ProjectSchedule::default())
+                .await?
+        } else if stop.is_some() || start.is_some() {
+            // Keep the part of the schedule that was not given
+            let current = client.get_project_schedule(pid).await?;
+            client
+                .set_project_schedule(
+                    pid,
+                    ProjectSchedule {
+                        stop: stop.or(current.stop),
+                        start: start.or(current.start),
+                    },
+                )
+                .await?
+        } else {
+            client.get_project_schedule(pid).await?
+        };
+        print!("{}", schedule.to_string_colored());
+
         Ok(())
     }
 
@@ -1651,8 +3203,13 @@ impl Shuttle {
         Ok(())
     }
 
-    fn make_archive(&self, secrets_file: Option<PathBuf>) -> Result<Vec<u8>> {
+    fn make_archive(
+        &self,
+        secrets_file: Option<PathBuf>,
+        merged_secrets: Option<&HashMap<String, String>>,
+    ) -> Result<Vec<u8>> {
         let include_patterns = self.ctx.include();
+        let exclude_patterns = self.ctx.exclude();
 
         let working_directory = self.ctx.working_directory();
 
@@ -1708,6 +3265,16 @@ impl Shuttle {
             }
         }
 
+        // User provided excludes, which take precedence over all includes
+        let mut exclude_globs = GlobSetBuilder::new();
+        if let Some(rules) = exclude_patterns {
+            for r in rules {
+                exclude_globs
+                    .add(Glob::new(r.as_str()).context(format!("parsing glob pattern {:?}", r))?);
+            }
+        }
+        let exclude_globs = exclude_globs.build().context("glob glob")?;
+
         let mut archive_files = BTreeMap::new();
         for path in entries {
             // It's not possible to add a directory to an archive
@@ -1731,11 +3298,22 @@ impl Shuttle {
             if secrets_file.as_ref().is_some_and(|sf| sf == &path) {
                 name.pop();
                 name.push("Secrets.toml");
+            } else if exclude_globs.is_match(&name) {
+                trace!("Skipping {:?}: is excluded in Shuttle.toml", path);
+                continue;
             }
 
             archive_files.insert(path, name);
         }
 
+        let merged_secrets = merged_secrets
+            .map(|secrets| toml::to_string(&secrets.iter().collect::<BTreeMap<_, _>>()))
+            .transpose()
+            .context("serializing merged secrets")?;
+        if merged_secrets.is_some() {
+            archive_files.retain(|_, name| name != Path::new("Secrets.toml"));
+        }
+
         if archive_files.is_empty() {
             error!("No files included in upload. Aborting...");
             bail!("No files included in upload.");
@@ -1755,6 +3333,11 @@ impl Shuttle {
                 File::open(path)?.read_to_end(&mut b)?;
                 zip.write_all(&b)?;
             }
+            if let Some(secrets) = merged_secrets {
+                debug!("Packing merged secrets");
+                zip.start_file("Secrets.toml", FileOptions::<()>::default())?;
+                zip.write_all(secrets.as_bytes())?;
+            }
             let r = zip.finish().context("finish encoding zip archive")?;
 
             r.into_inner()
@@ -1765,6 +3348,79 @@ impl Shuttle {
     }
 }
 
+/// List the files in a deployment archive with their sizes, and the archive size against the upload limit
+fn archive_listing(archive: &[u8]) -> Result<String> {
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive))
+        .context("reading deployment archive")?;
+    let mut s = String::new();
+    for i in 0..zip.len() {
+        let file = zip.by_index(i)?;
+        s.push_str(&format!(
+            "{:>10}  {}\n",
+            format_size(file.size() as usize),
+            file.name()
+        ));
+    }
+    s.push_str(&format!(
+        "\n{} files, {} compressed (limit {})\n",
+        zip.len(),
+        format_size(archive.len()),
+        format_size(CREATE_SERVICE_BODY_LIMIT)
+    ));
+    if archive.len() > CREATE_SERVICE_BODY_LIMIT {
+        s.push_str(&format!(
+            "{}\n",
+            "The archive is too large to deploy. Exclude files in Shuttle.toml or .ignore to make it smaller."
+                .red()
+        ));
+    }
+
+    Ok(s)
+}
+
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1_000 => format!("{bytes} B"),
+        1_000..1_000_000 => format!("{:.1} kB", bytes as f64 / 1e3),
+        _ => format!("{:.1} MB", bytes as f64 / 1e6),
+    }
+}
+
+/// Keep the first `head` or the last `tail` lines, or all of them if there are fewer
+fn slice_logs<T>(logs: &[T], head: Option<u32>, tail: Option<u32>) -> &[T] {
+    if let Some(n) = head {
+        &logs[..logs.len().min(n as usize)]
+    } else if let Some(n) = tail {
+        &logs[logs.len().saturating_sub(n as usize)..]
+    } else {
+        logs
+    }
+}
+
+/// Remove a container started by `cargo shuttle run --docker`, if it exists.
+/// Killing the `docker run` process leaves the container running.
+async fn remove_docker_container(container: &str) -> Result<()> {
+    let docker = bollard::Docker::connect_with_local_defaults()?;
+    match docker
+        .remove_container(
+            container,
+            Some(bollard::container::RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        )
+        .await
+    {
+        // Not found, or already being removed by `--rm`
+        Ok(())
+        | Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404 | 409,
+            ..
+        }) => Ok(()),
+        Err(e) => Err(e).context(format!("Failed to remove the container {container}")),
+    }
+}
+
 /// Calls async function `f` in a loop with `millis` sleep between iterations,
 /// providing iteration count and reference to update the progress bar.
 /// `f` returns Some with a cleanup function if done.
@@ -1816,12 +3472,79 @@ fn create_spinner() -> ProgressBar {
     pb
 }
 
+/// Parse a resource declared in the `[resources]` table of Shuttle.toml, like `postgres = "shared"`
+fn declared_resource_type(name: &str, kind: &str) -> Result<ResourceType> {
+    Ok(match (name, kind) {
+        ("postgres", "shared") => ResourceType::DatabaseSharedPostgres,
+        ("postgres", "rds") => ResourceType::DatabaseAwsRdsPostgres,
+        ("mysql", "shared") => ResourceType::DatabaseSharedMySql,
+        ("mysql", "rds") => ResourceType::DatabaseAwsRdsMySql,
+        ("mariadb", "rds") => ResourceType::DatabaseAwsRdsMariaDB,
+        ("redis", "shared") => ResourceType::CacheSharedRedis,
+        _ => bail!(
+            "Unknown resource `{name} = \"{kind}\"` in Shuttle.toml. \
+            Supported are postgres (shared, rds), mysql (shared, rds), mariadb (rds) and redis (shared)."
+        ),
+    })
+}
+
+fn is_rds(r#type: &ResourceType) -> bool {
+    matches!(
+        r#type,
+        ResourceType::DatabaseAwsRdsPostgres
+            | ResourceType::DatabaseAwsRdsMySql
+            | ResourceType::DatabaseAwsRdsMariaDB
+    )
+}
+
+/// Get the resources declared in Shuttle.toml that don't exist in the project yet
+fn missing_declared_resources(
+    declared: &BTreeMap<String, String>,
+    existing: &[ResourceType],
+) -> Result<Vec<ResourceType>> {
+    let mut new = Vec::new();
+    for (name, kind) in declared {
+        let r#type = declared_resource_type(name, kind)?;
+        if !existing.contains(&r#type) {
+            new.push(r#type);
+        }
+    }
+
+    Ok(new)
+}
+
+/// Check that the account has enough RDS subscriptions for the RDS databases of all its projects,
+/// so that a deployment does not fail after a full build.
+fn check_rds_subscriptions(rds_needed: usize, subscriptions: &[Subscription]) -> Result<()> {
+    let rds_allowed = subscriptions
+        .iter()
+        .filter(|s| s.r#type == SubscriptionType::Rds)
+        .map(|s| s.quantity.max(0) as usize)
+        .sum::<usize>();
+    if rds_needed > rds_allowed {
+        bail!(
+            "Your projects need {rds_needed} AWS RDS database(s), but your account has {rds_allowed} RDS subscription(s). \
+            Add one in the console before deploying: {SHUTTLE_CONSOLE_URL}"
+        );
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+    use shuttle_common::models::{
+        resource::ResourceType,
+        user::{Subscription, SubscriptionType},
+    };
     use zip::ZipArchive;
 
     use crate::args::{DeployArgs, ProjectArgs, SecretsArgs};
-    use crate::Shuttle;
+    use crate::{
+        check_rds_subscriptions, format_size, missing_declared_resources, slice_logs, Shuttle,
+        CREATE_SERVICE_BODY_LIMIT,
+    };
+    use std::collections::{BTreeMap, HashMap};
     use std::fs::{self, canonicalize};
     use std::io::Cursor;
     use std::path::PathBuf;
@@ -1845,7 +3568,7 @@ mod tests {
             .unwrap();
 
         let archive = shuttle
-            .make_archive(deploy_args.secret_args.secrets)
+            .make_archive(deploy_args.secret_args.secrets, None)
             .unwrap();
 
         let mut zip = ZipArchive::new(Cursor::new(archive)).unwrap();
@@ -1854,6 +3577,13 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn archive_sizes() {
+        assert_eq!(format_size(999), "999 B");
+        assert_eq!(format_size(1_500), "1.5 kB");
+        assert_eq!(format_size(CREATE_SERVICE_BODY_LIMIT), "50.0 MB");
+    }
+
     #[tokio::test]
     async fn make_archive_respect_rules() {
         let working_directory = canonicalize(path_from_workspace_root(
@@ -1893,6 +3623,7 @@ mod tests {
             "dist/dist1",            // .gitignore'd, but included in Shuttle.toml
             "nested/static/nested1", // normal file
             // nested/static/nestedignore is .gitignore'd
+            // nested/excluded/excluded1 is excluded in Shuttle.toml
             "src/main.rs",
         ];
         assert_eq!(entries, expected);
@@ -1927,11 +3658,52 @@ mod tests {
                 "dist/dist1",            // .gitignore'd, but included in Shuttle.toml
                 "nested/static/nested1", // normal file
                 // nested/static/nestedignore is .gitignore'd
+                // nested/excluded/excluded1 is excluded in Shuttle.toml
                 "src/main.rs",
             ]
         );
     }
 
+    #[test]
+    fn secrets_env_overlay() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Secrets.staging.toml"),
+            "API_KEY = 'staging'\nSTAGING_ONLY = 'yes'",
+        )
+        .unwrap();
+        let base = HashMap::from([
+            ("API_KEY".to_owned(), "prod".to_owned()),
+            ("DB_NAME".to_owned(), "app".to_owned()),
+        ]);
+
+        let secrets = Shuttle::overlay_secrets(Some(base), dir.path(), "staging").unwrap();
+        assert_eq!(
+            secrets,
+            HashMap::from([
+                ("API_KEY".to_owned(), "staging".to_owned()),
+                ("DB_NAME".to_owned(), "app".to_owned()),
+                ("STAGING_ONLY".to_owned(), "yes".to_owned()),
+            ])
+        );
+
+        assert!(Shuttle::overlay_secrets(None, dir.path(), "prod").is_err());
+    }
+
+    #[test]
+    fn log_ranges() {
+        let logs = [1, 2, 3, 4, 5];
+
+        assert_eq!(slice_logs(&logs, None, None), &logs);
+        assert_eq!(slice_logs(&logs, Some(2), None), &[1, 2]);
+        assert_eq!(slice_logs(&logs, None, Some(2)), &[4, 5]);
+        assert_eq!(slice_logs(&logs, Some(0), None), &[] as &[i32]);
+        // more lines than there are
+        assert_eq!(slice_logs(&logs, Some(10), None), &logs);
+        assert_eq!(slice_logs(&logs, None, Some(u32::MAX)), &logs);
+        assert_eq!(slice_logs(&[] as &[i32], Some(3), None), &[] as &[i32]);
+    }
+
     #[tokio::test]
     async fn finds_workspace_root() {
         let project_args = ProjectArgs {
@@ -1944,4 +3716,49 @@ mod tests {
             path_from_workspace_root("examples/axum/hello-world")
         );
     }
+
+    #[test]
+    fn declared_resources() {
+        let declared = BTreeMap::from([
+            ("postgres".to_owned(), "rds".to_owned()),
+            ("redis".to_owned(), "shared".to_owned()),
+        ]);
+
+        // already provisioned
+        assert_eq!(
+            missing_declared_resources(
+                &declared,
+                &[
+                    ResourceType::DatabaseAwsRdsPostgres,
+                    ResourceType::CacheSharedRedis
+                ],
+            )
+            .unwrap(),
+            vec![]
+        );
+        // new
+        assert_eq!(
+            missing_declared_resources(&declared, &[ResourceType::CacheSharedRedis]).unwrap(),
+            vec![ResourceType::DatabaseAwsRdsPostgres]
+        );
+        // unknown
+        let declared = BTreeMap::from([("postgres".to_owned(), "huge".to_owned())]);
+        assert!(missing_declared_resources(&declared, &[]).is_err());
+    }
+
+    #[test]
+    fn rds_subscriptions() {
+        let rds = Subscription {
+            id: "sub_1".to_owned(),
+            r#type: SubscriptionType::Rds,
+            quantity: 2,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        };
+
+        assert!(check_rds_subscriptions(2, std::slice::from_ref(&rds)).is_ok());
+        // another project of the account already uses the subscriptions
+        assert!(check_rds_subscriptions(3, &[rds]).is_err());
+        assert!(check_rds_subscriptions(1, &[]).is_err());
+    }
 }
@@ -5,8 +5,8 @@ use std::{
 
 use anyhow::{bail, Context, Result};
 use bollard::{
-    container::{Config, CreateContainerOptions, StartContainerOptions},
-    exec::{CreateExecOptions, CreateExecResults},
+    container::{Config, CreateContainerOptions, LogOutput, StartContainerOptions},
+    exec::{CreateExecOptions, CreateExecResults, StartExecResults},
     image::CreateImageOptions,
     models::{CreateImageInfo, HostConfig, PortBinding, ProgressDetail},
     service::ContainerInspectResponse,
@@ -33,9 +33,9 @@ use shuttle_common::{
     },
     secrets::Secret,
     tables::get_resource_tables,
-    ContainerRequest, ContainerResponse, DatabaseInfo, DbInput,
+    ContainerRequest, ContainerResponse, CustomResourceInput, DatabaseInfo, DbInput,
 };
-use tokio::{net::TcpListener, time::sleep};
+use tokio::{io::AsyncWriteExt, net::TcpListener, time::sleep};
 use tracing::{debug, error, trace};
 
 /// A provisioner for local runs
@@ -145,11 +145,13 @@ impl LocalProvisioner {
                     .await
                     .expect("to be able to create container");
 
-                Ok(self
+                let container = self
                     .docker
                     .inspect_container(container_name, None)
                     .await
-                    .expect("container to be created"))
+                    .expect("container to be created");
+
+                Ok(container)
             }
             Err(error) => {
                 error!("Got unexpected error while inspecting docker container: {error}");
@@ -166,6 +168,8 @@ impl LocalProvisioner {
         project_name: &str,
         db_type: ResourceType,
         db_name: Option<String>,
+        seeds: &HashMap<String, Vec<u8>>,
+        reseed: bool,
     ) -> Result<DatabaseInfo> {
         trace!("getting sql string for project '{project_name}'");
 
@@ -175,6 +179,8 @@ impl LocalProvisioner {
             | ResourceType::DatabaseAwsRdsMariaDB => {
                 db_name.unwrap_or_else(|| project_name.to_string())
             }
+            // Redis databases are numbered instead of named
+            ResourceType::CacheSharedRedis => "0".to_string(),
             _ => project_name.to_string(),
         };
 
@@ -187,6 +193,7 @@ impl LocalProvisioner {
             port,
             env,
             is_ready_cmd,
+            seed_cmd,
         } = db_type_to_config(db_type, &database_name);
         let container_name = format!("shuttle_{project_name}_{type}");
 
@@ -207,6 +214,19 @@ impl LocalProvisioner {
         sleep(Duration::from_millis(450)).await;
         self.wait_for_ready(&container_name, is_ready_cmd).await?;
 
+        // The data is kept in the container between runs, so only seed it once unless asked to
+        if let (Some(seed), Some(seed_cmd)) = (seeds.get(&engine), seed_cmd) {
+            if reseed || !self.is_seeded(&container_name).await? {
+                println!("Seeding {type} database in container '{container_name}'");
+                self.seed_database(&container_name, seed_cmd, seed)
+                    .await
+                    .context("Failed to seed database")?;
+                println!(
+                    "Seeded. Use `--reseed` to seed it again, or `docker rm -f {container_name}` to start from an empty database."
+                );
+            }
+        }
+
         let res = DatabaseInfo::new(
             engine,
             username,
@@ -282,6 +302,97 @@ impl LocalProvisioner {
         }
     }
 
+    /// Pipe the SQL into the database client inside the container, and mark the container
+    /// as seeded if it succeeds
+    async fn seed_database(
+        &self,
+        container_name: &str,
+        seed_cmd: Vec<String>,
+        seed: &[u8],
+    ) -> Result<()> {
+        let (exit_code, errors) = self.exec(container_name, seed_cmd, Some(seed)).await?;
+        if exit_code != Some(0) {
+            bail!(
+                "seed command exited with code {}: {}",
+                exit_code.unwrap_or(-1),
+                errors.trim()
+            );
+        }
+
+        let (exit_code, errors) = self
+            .exec(
+                container_name,
+                vec!["touch".to_string(), SEEDED_MARKER.to_string()],
+                None,
+            )
+            .await?;
+        if exit_code != Some(0) {
+            bail!("failed to mark database as seeded: {}", errors.trim());
+        }
+
+        Ok(())
+    }
+
+    /// Whether [`Self::seed_database`] succeeded in this container before
+    async fn is_seeded(&self, container_name: &str) -> Result<bool> {
+        let (exit_code, _) = self
+            .exec(
+                container_name,
+                vec![
+                    "test".to_string(),
+                    "-f".to_string(),
+                    SEEDED_MARKER.to_string(),
+                ],
+                None,
+            )
+            .await?;
+
+        Ok(exit_code == Some(0))
+    }
+
+    /// Run a command in the container and wait for it to finish.
+    /// Returns the exit code and what the command wrote to stderr.
+    async fn exec(
+        &self,
+        container_name: &str,
+        cmd: Vec<String>,
+        stdin: Option<&[u8]>,
+    ) -> Result<(Option<i64>, String)> {
+        let config = CreateExecOptions {
+            cmd: Some(cmd),
+            attach_stdin: Some(stdin.is_some()),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+        let CreateExecResults { id } = self.docker.create_exec(container_name, config).await?;
+
+        let mut errors = String::new();
+        if let StartExecResults::Attached {
+            mut output,
+            mut input,
+        } = self.docker.start_exec(&id, None).await?
+        {
+            if let Some(stdin) = stdin {
+                input.write_all(stdin).await?;
+            }
+            input.shutdown().await?;
+
+            while let Some(line) = output.next().await {
+                match line? {
+                    LogOutput::StdErr { message } => {
+                        errors.push_str(&String::from_utf8_lossy(&message))
+                    }
+                    line => trace!("exec output: {line}"),
+                }
+            }
+        }
+
+        let exit_code = self.docker.inspect_exec(&id).await?.exit_code;
+
+        Ok((exit_code, errors))
+    }
+
     async fn pull_image(&self, image: &str) -> Result<(), String> {
         trace!("pulling latest image for '{image}'");
         let mut layers = Vec::new();
@@ -368,8 +479,13 @@ struct EngineConfig {
     port: String,
     env: Option<Vec<String>>,
     is_ready_cmd: Vec<String>,
+    /// Command that runs SQL from stdin against the database
+    seed_cmd: Option<Vec<String>>,
 }
 
+/// File created in a database container after it was seeded
+const SEEDED_MARKER: &str = "/shuttle_seeded";
+
 fn db_type_to_config(db_type: ResourceType, database_name: &str) -> EngineConfig {
     match db_type {
         ResourceType::DatabaseSharedPostgres => EngineConfig {
@@ -388,6 +504,39 @@ fn db_type_to_config(db_type: ResourceType, database_name: &str) -> EngineConfig
                 "-c".to_string(),
                 "pg_isready | grep 'accepting connections'".to_string(),
             ],
+            seed_cmd: Some(vec![
+                "psql".to_string(),
+                "-U".to_string(),
+                "postgres".to_string(),
+                "-d".to_string(),
+                database_name.to_string(),
+                "-v".to_string(),
+                "ON_ERROR_STOP=1".to_string(),
+            ]),
+        },
+        ResourceType::DatabaseSharedMySql => EngineConfig {
+            r#type: "shared_mysql".to_string(),
+            image: "docker.io/library/mysql:8.0.28".to_string(),
+            engine: "mysql".to_string(),
+            username: "root".to_string(),
+            password: "mysql".to_string().into(),
+            port: "3306/tcp".to_string(),
+            env: Some(vec![
+                "MYSQL_ROOT_PASSWORD=mysql".to_string(),
+                format!("MYSQL_DATABASE={database_name}"),
+            ]),
+            is_ready_cmd: vec![
+                "mysql".to_string(),
+                "-pmysql".to_string(),
+                "--silent".to_string(),
+                "-e".to_string(),
+                "show databases;".to_string(),
+            ],
+            seed_cmd: Some(vec![
+                "mysql".to_string(),
+                "-pmysql".to_string(),
+                database_name.to_string(),
+            ]),
         },
         ResourceType::DatabaseAwsRdsPostgres => EngineConfig {
             r#type: "aws_rds_postgres".to_string(),
@@ -405,6 +554,15 @@ fn db_type_to_config(db_type: ResourceType, database_name: &str) -> EngineConfig
                 "-c".to_string(),
                 "pg_isready | grep 'accepting connections'".to_string(),
             ],
+            seed_cmd: Some(vec![
+                "psql".to_string(),
+                "-U".to_string(),
+                "postgres".to_string(),
+                "-d".to_string(),
+                database_name.to_string(),
+                "-v".to_string(),
+                "ON_ERROR_STOP=1".to_string(),
+            ]),
         },
         ResourceType::DatabaseAwsRdsMariaDB => EngineConfig {
             r#type: "aws_rds_mariadb".to_string(),
@@ -424,6 +582,11 @@ fn db_type_to_config(db_type: ResourceType, database_name: &str) -> EngineConfig
                 "-e".to_string(),
                 "show databases;".to_string(),
             ],
+            seed_cmd: Some(vec![
+                "mysql".to_string(),
+                "-pmariadb".to_string(),
+                database_name.to_string(),
+            ]),
         },
         ResourceType::DatabaseAwsRdsMySql => EngineConfig {
             r#type: "aws_rds_mysql".to_string(),
@@ -443,6 +606,23 @@ fn db_type_to_config(db_type: ResourceType, database_name: &str) -> EngineConfig
                 "-e".to_string(),
                 "show databases;".to_string(),
             ],
+            seed_cmd: Some(vec![
+                "mysql".to_string(),
+                "-pmysql".to_string(),
+                database_name.to_string(),
+            ]),
+        },
+        ResourceType::CacheSharedRedis => EngineConfig {
+            r#type: "shared_redis".to_string(),
+            image: "docker.io/library/redis:7".to_string(),
+            engine: "redis".to_string(),
+            // The default user of the image accepts any password
+            username: "default".to_string(),
+            password: "redis".to_string().into(),
+            port: "6379/tcp".to_string(),
+            env: None,
+            is_ready_cmd: vec!["redis-cli".to_string(), "ping".to_string()],
+            seed_cmd: None,
         },
         _ => panic!("Non-database resource type provided: {db_type}"),
     }
@@ -452,6 +632,10 @@ fn db_type_to_config(db_type: ResourceType, database_name: &str) -> EngineConfig
 pub struct ProvApiState {
     pub project_name: String,
     pub secrets: HashMap<String, String>,
+    /// SQL to run in database containers that were not seeded yet, by database engine
+    pub seeds: HashMap<String, Vec<u8>>,
+    /// Seed database containers even if they were seeded before
+    pub reseed: bool,
 }
 
 pub struct ProvisionerServer;
@@ -515,7 +699,7 @@ async fn provision(
                 config: serde_json::Value::Null,
                 output: serde_json::to_value(&state.secrets).unwrap(),
             };
-            let table = get_resource_tables(&[response.clone()], "local service", false, true);
+            let table = get_resource_tables(&[response.clone()], &[], "local service", false, true);
             println!("{table}");
             serde_json::to_vec(&response).unwrap()
         }
@@ -526,15 +710,19 @@ async fn provision(
 
             let response = match shuttle_resource.r#type {
                 ResourceType::DatabaseSharedPostgres
+                | ResourceType::DatabaseSharedMySql
                 | ResourceType::DatabaseAwsRdsMariaDB
                 | ResourceType::DatabaseAwsRdsMySql
-                | ResourceType::DatabaseAwsRdsPostgres => {
+                | ResourceType::DatabaseAwsRdsPostgres
+                | ResourceType::CacheSharedRedis => {
                     let config: DbInput = serde_json::from_value(shuttle_resource.config.clone())
                         .context("deserializing resource config")?;
                     let res = prov.get_db_connection_string(
                             &state.project_name,
-                            shuttle_resource.r#type,
+                            shuttle_resource.r#type.clone(),
                             config.db_name,
+                            &state.seeds,
+                            state.reseed,
                         )
                         .await
                         .context("Failed to start database container. Make sure that a Docker engine is running.")?;
@@ -564,9 +752,21 @@ async fn provision(
                     config: shuttle_resource.config,
                     output: serde_json::to_value(&state.secrets).unwrap(),
                 },
+                // Nothing to provision locally, so the plugin gets its own config back
+                ResourceType::Custom(_) => {
+                    let config: CustomResourceInput =
+                        serde_json::from_value(shuttle_resource.config.clone())
+                            .context("deserializing resource config")?;
+                    ResourceResponse {
+                        r#type: shuttle_resource.r#type,
+                        state: resource::ResourceState::Ready,
+                        config: shuttle_resource.config,
+                        output: config.config,
+                    }
+                }
             };
 
-            let table = get_resource_tables(&[response.clone()], "local service", false, true);
+            let table = get_resource_tables(&[response.clone()], &[], "local service", false, true);
             println!("{table}");
 
             serde_json::to_vec(&response).unwrap()
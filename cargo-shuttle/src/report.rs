@@ -0,0 +1,133 @@
+use std::{fmt::Write, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::CommandFactory;
+
+use crate::{
+    args::ShuttleArgs,
+    config::{ConfigManager, GlobalConfigManager},
+};
+
+/// How many log lines of a failed deployment are kept in a report
+pub const REPORT_LOG_LINES: usize = 50;
+
+/// Where the latest crash report is kept
+pub fn report_path() -> PathBuf {
+    GlobalConfigManager.directory().join("crash-report.md")
+}
+
+/// Save a report about a crash or failed deployment, replacing the previous report
+pub fn write_report(title: &str, details: &str, logs: &[String]) -> Result<PathBuf> {
+    let path = report_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, format_report(title, details, logs))
+        .with_context(|| format!("Could not write crash report to {}", path.display()))?;
+
+    Ok(path)
+}
+
+pub fn read_report() -> Result<String> {
+    fs::read_to_string(report_path()).context(
+        "No crash report found. Enable crash reports with `cargo shuttle telemetry enable`.",
+    )
+}
+
+/// Save a crash report when the CLI panics, in addition to the default panic output
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        if let Ok(path) = write_report(
+            "cargo-shuttle panicked",
+            &format!("{info}\n\n{backtrace}"),
+            &[],
+        ) {
+            eprintln!(
+                "A crash report was saved to {}. Use `cargo shuttle feedback --attach-report` to report the issue.",
+                path.display()
+            );
+        }
+    }));
+}
+
+/// The subcommands of a command line, such as `secrets set`.
+/// Other arguments can contain secrets, so they are left out.
+fn subcommand_path(args: impl IntoIterator<Item = String>) -> String {
+    let mut command = ShuttleArgs::command();
+    let mut path = Vec::new();
+    for arg in args.into_iter().skip_while(|arg| arg == "shuttle") {
+        let Some(sub) = command.find_subcommand(&arg) else {
+            break;
+        };
+        path.push(sub.get_name().to_owned());
+        command = sub.clone();
+    }
+
+    path.join(" ")
+}
+
+fn format_report(title: &str, details: &str, logs: &[String]) -> String {
+    let command = subcommand_path(std::env::args().skip(1));
+
+    let mut report = String::new();
+    writeln!(report, "### {title}\n").unwrap();
+    writeln!(report, "- cargo-shuttle: {}", crate::VERSION).unwrap();
+    writeln!(
+        report,
+        "- OS: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+    .unwrap();
+    writeln!(report, "- Command: `shuttle {command}`").unwrap();
+    writeln!(report, "- Time: {}", Utc::now().to_rfc3339()).unwrap();
+    if !details.is_empty() {
+        writeln!(report, "\n```text\n{}\n```", details.trim_end()).unwrap();
+    }
+    if !logs.is_empty() {
+        let skip = logs.len().saturating_sub(REPORT_LOG_LINES);
+        writeln!(
+            report,
+            "\nLast log lines:\n\n```text\n{}\n```",
+            logs[skip..].join("\n")
+        )
+        .unwrap();
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_keeps_last_log_lines() {
+        let logs = (0..60).map(|i| format!("line {i}")).collect::<Vec<_>>();
+        let report = format_report("Deployment failed", "", &logs);
+
+        assert!(report.starts_with("### Deployment failed\n"));
+        assert!(report.contains(&format!("- cargo-shuttle: {}", crate::VERSION)));
+        assert!(!report.contains("line 9\n"));
+        assert!(report.contains("line 10\n"));
+        assert!(report.contains("line 59\n```"));
+    }
+
+    #[test]
+    fn report_leaves_out_arguments() {
+        let args = |line: &str| line.split(' ').map(str::to_owned).collect::<Vec<_>>();
+
+        assert_eq!(
+            subcommand_path(args("shuttle secrets set API_KEY hunter2")),
+            "secrets set"
+        );
+        assert_eq!(subcommand_path(args("deploy --secrets s.toml")), "deploy");
+        // aliases are recorded by their name
+        assert_eq!(subcommand_path(args("project ls")), "project list");
+        assert_eq!(subcommand_path(args("hunter2")), "");
+    }
+}
@@ -14,11 +14,16 @@ use clap_mangen::Man;
 use futures::StreamExt;
 use git2::{Repository, StatusOptions};
 use indoc::writedoc;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use shuttle_common::{
-    constants::{SHUTTLE_GH_ISSUE_URL, SHUTTLE_GH_REPO_URL, SHUTTLE_INSTALL_DOCS_URL},
+    constants::{
+        SHUTTLE_GH_ISSUE_URL, SHUTTLE_GH_REPO_URL, SHUTTLE_INSTALL_DOCS_URL, STORAGE_DIRNAME,
+    },
+    models::deployment::{BuildMeta, GIT_STRINGS_MAX_LENGTH},
     semvers_are_compatible,
     templates::TemplatesSchema,
 };
+use tokio::sync::mpsc::Sender;
 use tokio_tungstenite::tungstenite::{self, Message};
 use tracing::{debug, trace, warn};
 
@@ -42,6 +47,53 @@ pub async fn get_templates_schema() -> Result<TemplatesSchema> {
     )?)
 }
 
+/// Watch a workspace recursively and send a notification on `tx` whenever a file changes.
+/// Changes to build artifacts, git internals and local storage are ignored.
+/// The watcher stops when the returned handle is dropped.
+pub fn watch_workspace(path: &Path, tx: Sender<()>) -> Result<RecommendedWatcher> {
+    let root = path.to_path_buf();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let event = match res {
+            Ok(event) => event,
+            Err(error) => {
+                warn!(%error, "file watcher error");
+                return;
+            }
+        };
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            return;
+        }
+        if event.paths.iter().all(|p| is_ignored_by_watcher(&root, p)) {
+            return;
+        }
+        trace!(paths = ?event.paths, "detected file change");
+        // A full channel means a rebuild is already pending
+        let _ = tx.try_send(());
+    })
+    .context("failed to create file watcher")?;
+
+    watcher
+        .watch(path, RecursiveMode::Recursive)
+        .context("failed to watch workspace")?;
+
+    Ok(watcher)
+}
+
+fn is_ignored_by_watcher(root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .any(|c| {
+            matches!(
+                c.as_os_str().to_str(),
+                Some("target" | ".git" | STORAGE_DIRNAME)
+            )
+        })
+}
+
 pub fn is_dirty(repo: &Repository) -> Result<()> {
     let mut status_options = StatusOptions::new();
     status_options.include_untracked(true);
@@ -76,6 +128,30 @@ pub fn is_dirty(repo: &Repository) -> Result<()> {
     Ok(())
 }
 
+/// Get the branch and commit info of the HEAD of a repository
+pub fn git_build_meta(repo: &Repository, dirty: bool) -> BuildMeta {
+    let mut build_meta = BuildMeta {
+        git_dirty: Some(dirty),
+        ..Default::default()
+    };
+    if let Ok(head) = repo.head() {
+        // This is typically the name of the current branch
+        // It is "HEAD" when head detached, for example when a tag is checked out
+        build_meta.git_branch = head
+            .shorthand()
+            .map(|s| s.chars().take(GIT_STRINGS_MAX_LENGTH).collect());
+        if let Ok(commit) = head.peel_to_commit() {
+            build_meta.git_commit_id = Some(commit.id().to_string());
+            // Summary is None if error or invalid utf-8
+            build_meta.git_commit_msg = commit
+                .summary()
+                .map(|s| s.chars().take(GIT_STRINGS_MAX_LENGTH).collect());
+        }
+    }
+
+    build_meta
+}
+
 pub async fn check_and_warn_runtime_version(path: &Path) -> Result<Option<String>> {
     if let Err(err) = check_version(path).await {
         warn!("{}", err);
@@ -90,7 +166,7 @@ pub async fn check_and_warn_runtime_version(path: &Path) -> Result<Option<String
                     &mut warning,
                     "
                     Hint: A newer version of Shuttle CLI is available.
-                          Check out the installation docs for how to update: {}
+                          Run `shuttle upgrade` to update it, or check out the installation docs: {}
                     ",
                     SHUTTLE_INSTALL_DOCS_URL,
                 }
@@ -231,46 +307,134 @@ pub fn generate_manpage() -> Result<()> {
     Ok(())
 }
 
-pub fn open_gh_issue() -> Result<()> {
-    let _ = webbrowser::open(SHUTTLE_GH_ISSUE_URL);
-    eprintln!("If your browser did not open automatically, go to {SHUTTLE_GH_ISSUE_URL}");
+pub fn open_gh_issue(attach_report: bool) -> Result<()> {
+    let url = if attach_report {
+        let mut report = crate::report::read_report()?;
+        // Keep the URL within what browsers and GitHub accept
+        if report.len() > 6000 {
+            let mut end = 6000;
+            while !report.is_char_boundary(end) {
+                end -= 1;
+            }
+            report.truncate(end);
+            report.push_str("\n...");
+        }
+        url::Url::parse_with_params(
+            &format!("{SHUTTLE_GH_REPO_URL}/issues/new"),
+            [("body", report)],
+        )?
+        .to_string()
+    } else {
+        SHUTTLE_GH_ISSUE_URL.to_owned()
+    };
+    let _ = webbrowser::open(&url);
+    eprintln!("If your browser did not open automatically, go to {url}");
 
     Ok(())
 }
 
-pub async fn update_cargo_shuttle(preview: bool) -> Result<()> {
-    if preview {
-        let _ = tokio::process::Command::new("cargo")
-            .args(["install", "cargo-shuttle", "--git", SHUTTLE_GH_REPO_URL])
-            .kill_on_drop(true)
-            .spawn()
-            .context("Failed to spawn cargo install process")?
-            .wait()
-            .await
-            .context("Failed to wait on cargo install process")?;
-
-        return Ok(());
+/// Get the version of the latest cargo-shuttle release from crates.io
+async fn latest_cargo_shuttle_version() -> Result<semver::Version> {
+    #[derive(serde::Deserialize)]
+    struct CrateResponse {
+        #[serde(rename = "crate")]
+        krate: CrateInfo,
+    }
+    #[derive(serde::Deserialize)]
+    struct CrateInfo {
+        max_stable_version: String,
     }
 
-    #[cfg(target_family = "unix")]
-    let _ = tokio::process::Command::new("bash")
-        .args(["-c", "curl -sSfL https://www.shuttle.dev/install | bash"])
+    let res: CrateResponse = reqwest::Client::new()
+        .get("https://crates.io/api/v1/crates/cargo-shuttle")
+        // crates.io requires a user agent
+        .header(
+            reqwest::header::USER_AGENT,
+            format!("cargo-shuttle/{}", crate::VERSION),
+        )
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(res.krate.max_stable_version.parse()?)
+}
+
+/// Run a command and wait for it, returning whether it succeeded
+async fn run_install_command(program: &str, args: &[&str]) -> Result<bool> {
+    Ok(tokio::process::Command::new(program)
+        .args(args)
         .kill_on_drop(true)
         .spawn()
-        .context("Failed to spawn bash update process")?
+        .with_context(|| format!("Failed to spawn {program} update process"))?
         .wait()
         .await
-        .context("Failed to wait on bash update process")?;
+        .with_context(|| format!("Failed to wait on {program} update process"))?
+        .success())
+}
 
-    #[cfg(target_family = "windows")]
-    let _ = tokio::process::Command::new("powershell")
-        .args(["-Command", "iwr https://www.shuttle.dev/install-win | iex"])
-        .kill_on_drop(true)
-        .spawn()
-        .context("Failed to spawn powershell update process")?
-        .wait()
+/// Check if a program can be run, without showing its output
+async fn can_run(program: &str, args: &[&str]) -> bool {
+    tokio::process::Command::new(program)
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
         .await
-        .context("Failed to wait on powershell update process")?;
+        .is_ok_and(|s| s.success())
+}
+
+pub async fn update_cargo_shuttle(preview: bool) -> Result<()> {
+    if preview {
+        run_install_command(
+            "cargo",
+            &["install", "cargo-shuttle", "--git", SHUTTLE_GH_REPO_URL],
+        )
+        .await?;
+
+        return Ok(());
+    }
+
+    let current: semver::Version = crate::VERSION.parse()?;
+    match latest_cargo_shuttle_version().await {
+        Ok(latest) if latest <= current => {
+            println!("cargo-shuttle {current} is the latest version");
+            return Ok(());
+        }
+        Ok(latest) => println!("Upgrading cargo-shuttle {current} -> {latest}"),
+        Err(e) => {
+            debug!(error = %e, "failed to get latest cargo-shuttle version");
+            eprintln!("Could not check for the latest version, upgrading anyway");
+        }
+    }
+
+    // Prefer prebuilt binaries, and only compile from source when no installer can be run
+    let success = if can_run("cargo", &["binstall", "-V"]).await {
+        run_install_command("cargo", &["binstall", "-y", "cargo-shuttle"]).await?
+    } else if cfg!(target_family = "windows") && can_run("powershell", &["-Command", "exit"]).await
+    {
+        run_install_command(
+            "powershell",
+            &["-Command", "iwr https://www.shuttle.dev/install-win | iex"],
+        )
+        .await?
+    } else if cfg!(target_family = "unix") && can_run("bash", &["-c", "command -v curl"]).await {
+        run_install_command(
+            "bash",
+            &["-c", "curl -sSfL https://www.shuttle.dev/install | bash"],
+        )
+        .await?
+    } else {
+        run_install_command("cargo", &["install", "cargo-shuttle", "--locked"]).await?
+    };
+
+    if !success {
+        bail!(
+            "Upgrading failed. Check out the installation docs for other ways to update: {}",
+            SHUTTLE_INSTALL_DOCS_URL
+        );
+    }
 
     Ok(())
 }
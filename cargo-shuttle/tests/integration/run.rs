@@ -30,6 +30,7 @@ async fn shuttle_run(working_directory: &str, external: bool) -> String {
     let runner = Shuttle::new(cargo_shuttle::Binary::Shuttle).unwrap().run(
         ShuttleArgs {
             api_url: Some("http://shuttle.invalid:80".to_string()),
+            profile: None,
             project_args: ProjectArgs {
                 working_directory: working_directory.clone(),
                 name_or_id: None,
@@ -41,6 +42,11 @@ async fn shuttle_run(working_directory: &str, external: bool) -> String {
                 external,
                 release: false,
                 raw: false,
+                watch: false,
+                port_map: Vec::new(),
+                docker: false,
+                seed: Vec::new(),
+                reseed: false,
                 secret_args: Default::default(),
             }),
         },
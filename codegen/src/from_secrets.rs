@@ -0,0 +1,193 @@
+use proc_macro::TokenStream;
+use proc_macro_error2::abort;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Field, Fields, GenericArgument, LitStr, PathArguments,
+    Type,
+};
+
+pub(crate) fn tokens(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    impl_from_secrets(&input).into()
+}
+
+fn impl_from_secrets(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => abort!(
+                ident,
+                "FromSecrets can only be derived for structs with named fields"
+            ),
+        },
+        _ => abort!(ident, "FromSecrets can only be derived for structs"),
+    };
+
+    let lookups = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let key = secret_key(field);
+        match option_inner(&field.ty) {
+            Some(ty) => quote! {
+                let #field_ident = match ::shuttle_runtime::__internals::parse_secret::<#ty>(secrets, #key) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        errors.push(e);
+                        None
+                    }
+                };
+            },
+            None => {
+                let ty = &field.ty;
+                quote! {
+                    let #field_ident = match ::shuttle_runtime::__internals::parse_secret::<#ty>(secrets, #key) {
+                        Ok(Some(value)) => Some(value),
+                        Ok(None) => {
+                            errors.push(format!("secret `{}` is missing", #key));
+                            None
+                        }
+                        Err(e) => {
+                            errors.push(e);
+                            None
+                        }
+                    };
+                }
+            }
+        }
+    });
+
+    let assignments = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        match option_inner(&field.ty) {
+            Some(_) => quote!(#field_ident),
+            None => quote!(#field_ident: #field_ident.expect("errors to be checked")),
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::shuttle_runtime::FromSecrets for #ident #ty_generics #where_clause {
+            fn from_secrets(
+                secrets: &::shuttle_runtime::SecretStore,
+            ) -> ::std::result::Result<Self, ::shuttle_runtime::Error> {
+                let mut errors: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(#lookups)*
+                if !errors.is_empty() {
+                    return Err(::shuttle_runtime::Error::Custom(
+                        ::shuttle_runtime::CustomError::msg(format!("invalid secrets: {}", errors.join("; ")))
+                    ));
+                }
+
+                Ok(Self {
+                    #(#assignments,)*
+                })
+            }
+        }
+    }
+}
+
+/// The key of a field is set with `#[secret(key = "...")]`, and is the field name in upper case otherwise
+fn secret_key(field: &Field) -> String {
+    let mut key = field
+        .ident
+        .as_ref()
+        .expect("named field")
+        .to_string()
+        .to_uppercase();
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("secret")) {
+        let res = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported secret option, expected `key`"))
+            }
+        });
+        if let Err(err) = res {
+            abort!(err.span(), err);
+        }
+    }
+
+    key
+}
+
+/// Get `T` if this is an `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn output() {
+        let input: DeriveInput = parse_quote!(
+            struct Config {
+                api_key: String,
+                #[secret(key = "sentry-dsn")]
+                sentry_dsn: Option<String>,
+            }
+        );
+
+        let actual = impl_from_secrets(&input);
+        let expected = quote! {
+            impl ::shuttle_runtime::FromSecrets for Config {
+                fn from_secrets(
+                    secrets: &::shuttle_runtime::SecretStore,
+                ) -> ::std::result::Result<Self, ::shuttle_runtime::Error> {
+                    let mut errors: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                    let api_key = match ::shuttle_runtime::__internals::parse_secret::<String>(secrets, "API_KEY") {
+                        Ok(Some(value)) => Some(value),
+                        Ok(None) => {
+                            errors.push(format!("secret `{}` is missing", "API_KEY"));
+                            None
+                        }
+                        Err(e) => {
+                            errors.push(e);
+                            None
+                        }
+                    };
+                    let sentry_dsn = match ::shuttle_runtime::__internals::parse_secret::<String>(secrets, "sentry-dsn") {
+                        Ok(value) => value,
+                        Err(e) => {
+                            errors.push(e);
+                            None
+                        }
+                    };
+                    if !errors.is_empty() {
+                        return Err(::shuttle_runtime::Error::Custom(
+                            ::shuttle_runtime::CustomError::msg(format!("invalid secrets: {}", errors.join("; ")))
+                        ));
+                    }
+
+                    Ok(Self {
+                        api_key: api_key.expect("errors to be checked"),
+                        sentry_dsn,
+                    })
+                }
+            }
+        };
+
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
@@ -1,3 +1,4 @@
+mod from_secrets;
 mod shuttle_main;
 
 /// Helper macro that generates the entrypoint required by any service - likely the only macro you need in this crate.
@@ -57,3 +58,29 @@ pub fn main(
 ) -> proc_macro::TokenStream {
     shuttle_main::tokens(attr, item)
 }
+
+/// Derive `shuttle_runtime::FromSecrets` to get the secrets of a deployment as a typed config struct.
+///
+/// Each field is read from the secret with the field name in upper case, or from the key set with `#[secret(key = "...")]`.
+/// Fields are parsed with [`FromStr`](std::str::FromStr), and `Option` fields are allowed to be missing.
+/// All missing and invalid secrets are reported together when the service starts.
+///
+/// ```rust,ignore
+/// #[derive(shuttle_runtime::FromSecrets)]
+/// struct Config {
+///     api_key: String,
+///     port: u16,
+///     #[secret(key = "sentry-dsn")]
+///     sentry_dsn: Option<String>,
+/// }
+///
+/// #[shuttle_runtime::main]
+/// async fn main(#[shuttle_runtime::Secrets] config: Config) -> ShuttleAxum {
+///     ...
+/// }
+/// ```
+#[proc_macro_error2::proc_macro_error]
+#[proc_macro_derive(FromSecrets, attributes(secret))]
+pub fn derive_from_secrets(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    from_secrets::tokens(item)
+}
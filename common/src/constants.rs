@@ -17,6 +17,9 @@ pub const EXAMPLES_README: &str =
 pub const EXAMPLES_TEMPLATES_TOML: &str =
     "https://raw.githubusercontent.com/shuttle-hq/shuttle-examples/main/templates.toml";
 
+/// Maximum size in bytes of a deployment archive accepted by the API
+pub const CREATE_SERVICE_BODY_LIMIT: usize = 50_000_000;
+
 /// Crate name for checking cargo metadata
 pub const RUNTIME_NAME: &str = "shuttle-runtime";
 
@@ -16,6 +16,15 @@ pub struct DbInput {
     pub local_uri: Option<String>,
     /// Override the default db name. Only applies to RDS.
     pub db_name: Option<String>,
+    /// Override the default instance class. Only applies to RDS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_class: Option<String>,
+    /// Override the default allocated storage in GB. Only applies to RDS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocated_storage: Option<u32>,
+    /// Override the default engine version. Only applies to RDS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_version: Option<String>,
 }
 
 /// The output produced by Shuttle DB resources
@@ -99,6 +108,18 @@ impl std::fmt::Debug for DatabaseInfo {
     }
 }
 
+/// Config of a [`models::resource::ResourceType::Custom`] resource.
+///
+/// The config and output of custom resources are kept by Shuttle like those of other resources,
+/// so that a third-party plugin can get the same output back on every deployment.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CustomResourceInput {
+    /// Identifies the plugin that the resource belongs to. ex "meilisearch"
+    pub type_id: String,
+    /// Plugin specific config
+    pub config: serde_json::Value,
+}
+
 /// Used to request a container from the local run provisioner
 #[derive(Serialize, Deserialize)]
 pub struct ContainerRequest {
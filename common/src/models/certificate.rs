@@ -1,5 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "display")]
+use crossterm::style::Stylize;
+#[cfg(feature = "display")]
+use std::fmt::Write;
+
 #[derive(Deserialize, Serialize, Debug)]
 #[typeshare::typeshare]
 pub struct AddCertificateRequest {
@@ -14,6 +20,101 @@ pub struct DeleteCertificateRequest {
     pub subject: String,
 }
 
+/// Check the DNS records of a custom domain before a certificate is requested for it
+#[derive(Deserialize, Serialize, Debug)]
+#[typeshare::typeshare]
+pub struct VerifyDomainRequest {
+    #[serde(alias = "domain")]
+    pub subject: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[typeshare::typeshare]
+pub struct DomainVerificationResponse {
+    pub subject: String,
+    /// All records are correct, so a certificate can be issued
+    pub ready: bool,
+    pub records: Vec<DnsRecordCheck>,
+}
+
+/// A DNS record that a custom domain needs
+#[derive(Deserialize, Serialize, Debug)]
+#[typeshare::typeshare]
+pub struct DnsRecordCheck {
+    /// Record type, e.g. CNAME or TXT
+    pub record_type: String,
+    pub name: String,
+    /// Value the record should have
+    pub expected: String,
+    /// Values currently found in DNS
+    pub found: Vec<String>,
+    pub ok: bool,
+    /// What is wrong with the record, e.g. that the CNAME points to another target
+    pub error: Option<String>,
+}
+
+impl DomainVerificationResponse {
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = String::new();
+        for record in &self.records {
+            let status = if record.ok {
+                "ok".green()
+            } else {
+                "wrong".red()
+            };
+            writeln!(
+                &mut s,
+                "{} {} {}: {status}",
+                record.record_type.as_str().bold(),
+                record.name,
+                record.expected,
+            )
+            .unwrap();
+            if !record.ok {
+                let found = if record.found.is_empty() {
+                    "nothing".to_owned()
+                } else {
+                    record.found.join(", ")
+                };
+                writeln!(&mut s, "  found: {found}").unwrap();
+                if let Some(error) = &record.error {
+                    writeln!(&mut s, "  {error}").unwrap();
+                }
+            }
+        }
+        if self.ready {
+            writeln!(&mut s, "{} is ready for a certificate", self.subject).unwrap();
+        } else {
+            writeln!(
+                &mut s,
+                "{}",
+                format!(
+                    "Fix the records above for {} at your DNS provider. Changes can take a while to propagate.",
+                    self.subject
+                )
+                .yellow()
+            )
+            .unwrap();
+        }
+
+        s
+    }
+}
+
+/// Change how requests to a custom domain are handled.
+/// Set wanted field(s) to Some to update those settings.
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[typeshare::typeshare]
+pub struct UpdateCertificateRequest {
+    #[serde(alias = "domain")]
+    pub subject: String,
+    /// Send the Strict-Transport-Security header on HTTPS responses
+    pub hsts: Option<bool>,
+    /// Path prefixes that are served over plain HTTP instead of being redirected to HTTPS
+    pub http_paths: Option<Vec<String>>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 #[typeshare::typeshare]
 pub struct CertificateResponse {
@@ -21,6 +122,14 @@ pub struct CertificateResponse {
     pub subject: String,
     pub serial_hex: String,
     pub not_after: String,
+    #[serde(default = "default_hsts")]
+    pub hsts: bool,
+    #[serde(default)]
+    pub http_paths: Vec<String>,
+}
+
+fn default_hsts() -> bool {
+    true
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -28,3 +137,24 @@ pub struct CertificateResponse {
 pub struct CertificateListResponse {
     pub certificates: Vec<CertificateResponse>,
 }
+
+/// Expiry and renewal state of a custom domain certificate, for the admin API
+#[derive(Deserialize, Serialize, Debug)]
+#[typeshare::typeshare]
+pub struct AdminCertificateStatus {
+    pub project_id: String,
+    pub subject: String,
+    pub not_after: String,
+    /// When the last ACME renewal was attempted
+    pub last_renewal_at: Option<DateTime<Utc>>,
+    /// Why the last renewal failed, if it did
+    pub last_renewal_error: Option<String>,
+    /// Whether an OCSP response is currently stapled for this certificate
+    pub ocsp_stapled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[typeshare::typeshare]
+pub struct AdminCertificateStatusListResponse {
+    pub certificates: Vec<AdminCertificateStatus>,
+}
@@ -81,6 +81,106 @@ pub struct DeploymentResponse {
     pub uris: Vec<String>,
     pub build_id: Option<String>,
     pub build_meta: Option<BuildMeta>,
+    /// Version of shuttle-runtime the service was built with
+    #[serde(default)]
+    pub runtime_version: Option<String>,
+    /// Names of the secrets that were set for this deployment
+    #[serde(default)]
+    pub secret_keys: Option<Vec<String>>,
+    /// Error or panic message of the service if this deployment crashed
+    #[serde(default)]
+    pub crash_reason: Option<String>,
+}
+
+/// A difference between two deployments
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeploymentChange {
+    pub field: &'static str,
+    pub from: String,
+    pub to: String,
+}
+
+impl DeploymentResponse {
+    /// What is different in the `to` deployment compared to this one
+    pub fn diff(&self, to: &Self) -> Vec<DeploymentChange> {
+        fn or_none(value: Option<&str>) -> String {
+            value.unwrap_or("(none)").to_owned()
+        }
+        fn commit(meta: &Option<BuildMeta>) -> Option<String> {
+            let meta = meta.as_ref()?;
+            let id = meta
+                .git_commit_id
+                .as_ref()?
+                .chars()
+                .take(7)
+                .collect::<String>();
+            Some(match meta.git_dirty {
+                Some(true) => format!("{id} (dirty)"),
+                _ => id,
+            })
+        }
+        fn branch(meta: &Option<BuildMeta>) -> Option<&str> {
+            meta.as_ref()?.git_branch.as_deref()
+        }
+
+        let mut changes = Vec::new();
+        let mut push = |field, from: String, to: String| {
+            if from != to {
+                changes.push(DeploymentChange { field, from, to });
+            }
+        };
+        push(
+            "Git commit",
+            or_none(commit(&self.build_meta).as_deref()),
+            or_none(commit(&to.build_meta).as_deref()),
+        );
+        push(
+            "Git branch",
+            or_none(branch(&self.build_meta)),
+            or_none(branch(&to.build_meta)),
+        );
+        push(
+            "Build",
+            or_none(self.build_id.as_deref()),
+            or_none(to.build_id.as_deref()),
+        );
+        push(
+            "Runtime version",
+            or_none(self.runtime_version.as_deref()),
+            or_none(to.runtime_version.as_deref()),
+        );
+
+        // Older deployments don't have the secret names
+        if let (Some(from_keys), Some(to_keys)) = (&self.secret_keys, &to.secret_keys) {
+            let removed = from_keys
+                .iter()
+                .filter(|k| !to_keys.contains(k))
+                .cloned()
+                .collect::<Vec<_>>();
+            let added = to_keys
+                .iter()
+                .filter(|k| !from_keys.contains(k))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !removed.is_empty() || !added.is_empty() {
+                let list = |keys: Vec<String>, sign: char| {
+                    let list = keys
+                        .iter()
+                        .map(|k| format!("{sign}{k}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    or_none(Some(list.as_str()).filter(|l| !l.is_empty()))
+                };
+                changes.push(DeploymentChange {
+                    field: "Secrets",
+                    from: list(removed, '-'),
+                    to: list(added, '+'),
+                });
+            }
+        }
+
+        changes
+    }
 }
 
 #[cfg(feature = "display")]
@@ -95,12 +195,17 @@ impl DeploymentResponse {
     }
     pub fn to_string_colored(&self) -> String {
         // TODO: make this look nicer
-        format!(
+        let mut s = format!(
             "Deployment {} - {}\n{}",
             self.id.as_str().bold(),
             self.state.to_string_colored(),
             self.uris.join("\n"),
-        )
+        );
+        if let Some(ref reason) = self.crash_reason {
+            s.push_str(&format!("\nCrash reason:\n{}", reason.as_str().red()));
+        }
+
+        s
     }
 }
 
@@ -116,13 +221,13 @@ pub struct UploadArchiveResponse {
 #[typeshare::typeshare]
 pub enum DeploymentRequest {
     /// Build an image from the source code in an attached zip archive
-    BuildArchive(DeploymentRequestBuildArchive),
+    BuildArchive(Box<DeploymentRequestBuildArchive>),
     // TODO?: Add GitRepo(DeploymentRequestGitRepo)
     /// Use this image directly. Can be used to skip the build step.
     Image(DeploymentRequestImage),
 }
 
-#[derive(Default, Deserialize, Serialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[typeshare::typeshare]
 pub struct DeploymentRequestBuildArchive {
     /// The S3 object version ID of the archive to use
@@ -132,9 +237,15 @@ pub struct DeploymentRequestBuildArchive {
     /// TODO: Remove this in favour of a separate secrets uploading action.
     pub secrets: Option<HashMap<String, String>>,
     pub build_meta: Option<BuildMeta>,
+    /// HTTP path on the service that is polled to decide if the deployment is healthy
+    pub health_check_path: Option<String>,
+    /// Non-secret environment variables for the service, kept with this deployment
+    pub env: Option<HashMap<String, String>>,
+    /// Tokens by git host, for fetching private git dependencies during this build only
+    pub git_credentials: Option<HashMap<String, String>>,
 }
 
-#[derive(Deserialize, Serialize, Default)]
+#[derive(Clone, Deserialize, Serialize, Default)]
 #[serde(tag = "type", content = "content")]
 #[typeshare::typeshare]
 pub enum BuildArgs {
@@ -143,7 +254,7 @@ pub enum BuildArgs {
     Unknown,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[typeshare::typeshare]
 pub struct BuildArgsRust {
     /// Version of shuttle-runtime used by this crate
@@ -162,6 +273,8 @@ pub struct BuildArgsRust {
     pub no_default_features: bool,
     /// Use the mold linker
     pub mold: bool,
+    /// Skip the cargo registry and target cache from previous builds of this project
+    pub no_cache: bool,
 }
 
 impl Default for BuildArgsRust {
@@ -175,6 +288,7 @@ impl Default for BuildArgsRust {
             features: Default::default(),
             no_default_features: Default::default(),
             mold: Default::default(),
+            no_cache: Default::default(),
         }
     }
 }
@@ -182,7 +296,7 @@ impl Default for BuildArgsRust {
 /// Max length of strings in the git metadata
 pub const GIT_STRINGS_MAX_LENGTH: usize = 80;
 
-#[derive(Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[typeshare::typeshare]
 pub struct BuildMeta {
     pub git_commit_id: Option<String>,
@@ -208,21 +322,66 @@ impl std::fmt::Display for BuildMeta {
     }
 }
 
+/// Sizes of the caches kept between builds of a project, in megabytes.
+/// After a clean, the sizes that were freed.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[typeshare::typeshare]
+pub struct BuildCacheResponse {
+    /// Cargo target directory
+    pub target_mb: Option<f64>,
+    /// Downloaded crates
+    pub registry_mb: Option<f64>,
+}
+
+#[cfg(feature = "display")]
+impl BuildCacheResponse {
+    pub fn to_string_colored(&self) -> String {
+        let size = |mb: Option<f64>| match mb {
+            Some(mb) => format!("{mb:.1} MB"),
+            None => "none".dark_grey().to_string(),
+        };
+        format!(
+            "  Target directory: {}\n  Crate registry: {}\n",
+            size(self.target_mb),
+            size(self.registry_mb)
+        )
+    }
+}
+
 #[derive(Default, Deserialize, Serialize)]
 #[typeshare::typeshare]
 pub struct DeploymentRequestImage {
     pub image: String,
     /// TODO: Remove this in favour of a separate secrets uploading action.
     pub secrets: Option<HashMap<String, String>>,
+    /// HTTP path on the service that is polled to decide if the deployment is healthy
+    pub health_check_path: Option<String>,
+    /// Non-secret environment variables for the service, kept with this deployment
+    pub env: Option<HashMap<String, String>>,
     // TODO: credentials fields for private repos??
 }
 
+/// Deploy the image built for a deployment to another project owned by the same account.
+/// Resource configuration is copied to the target project, but not the data in the resources.
+#[derive(Deserialize, Serialize)]
+#[typeshare::typeshare]
+pub struct DeploymentPromoteRequest {
+    /// Project ID to deploy to
+    pub project_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentMetadata {
     pub env: Environment,
     pub project_name: String,
     /// Path to a folder that persists between deployments
     pub storage_path: PathBuf,
+    /// ID of the running deployment. Not set in local runs.
+    #[serde(default)]
+    pub deployment_id: Option<String>,
+    /// Git info of the source code this deployment was built from
+    #[serde(default)]
+    pub build_meta: Option<BuildMeta>,
 }
 
 /// The environment this project is running in
@@ -259,6 +418,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deployment_diff() {
+        let deployment = |commit: &str, version: &str, secrets: &[&str]| DeploymentResponse {
+            id: "depl_1".to_owned(),
+            state: DeploymentState::Running,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+            uris: vec![],
+            build_id: Some("bld_1".to_owned()),
+            build_meta: Some(BuildMeta {
+                git_commit_id: Some(commit.to_owned()),
+                git_branch: Some("main".to_owned()),
+                ..Default::default()
+            }),
+            runtime_version: Some(version.to_owned()),
+            secret_keys: Some(secrets.iter().map(|s| s.to_string()).collect()),
+            crash_reason: None,
+        };
+
+        let from = deployment("0123456789", "0.50.0", &["API_KEY", "OLD"]);
+        let to = deployment("abcdef0123", "0.51.0", &["API_KEY", "NEW"]);
+
+        assert_eq!(from.diff(&from), vec![]);
+        assert_eq!(
+            from.diff(&to),
+            vec![
+                DeploymentChange {
+                    field: "Git commit",
+                    from: "0123456".to_owned(),
+                    to: "abcdef0".to_owned(),
+                },
+                DeploymentChange {
+                    field: "Runtime version",
+                    from: "0.50.0".to_owned(),
+                    to: "0.51.0".to_owned(),
+                },
+                DeploymentChange {
+                    field: "Secrets",
+                    from: "-OLD".to_owned(),
+                    to: "+NEW".to_owned(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_env_deser() {
         assert_eq!(Environment::Local, Environment::from_str("local").unwrap());
@@ -1,4 +1,9 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
+#[cfg(feature = "display")]
+use std::fmt::Write;
+
 #[cfg(feature = "display")]
 use crossterm::style::Stylize;
 use serde::{Deserialize, Serialize};
@@ -10,6 +15,9 @@ pub struct LogItem {
     /// Which container / log stream this line came from
     pub source: String,
     pub line: String,
+    /// Fields of a structured log event (e.g. from `tracing`), kept apart from the message in `line`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 impl LogItem {
@@ -18,6 +26,54 @@ impl LogItem {
             timestamp,
             source,
             line,
+            fields: None,
+        }
+    }
+}
+
+/// How to show timestamps of log lines
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum LogTimestamps {
+    /// In the local timezone
+    #[default]
+    Local,
+    Utc,
+    /// Leave them out
+    Off,
+}
+
+#[cfg(feature = "display")]
+impl LogItem {
+    /// Format this log line with the given timestamps, and with or without colors
+    pub fn format(&self, timestamps: LogTimestamps, color: bool) -> String {
+        let timestamp = match timestamps {
+            LogTimestamps::Local => Some(
+                DateTime::<chrono::Local>::from(self.timestamp)
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            ),
+            LogTimestamps::Utc => Some(
+                self.timestamp
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            ),
+            LogTimestamps::Off => None,
+        };
+        let mut line = if color {
+            self.line.clone()
+        } else {
+            strip_ansi(&self.line)
+        };
+        for (key, value) in self.fields.iter().flatten() {
+            match value {
+                serde_json::Value::String(s) => write!(line, " {key}={s}").unwrap(),
+                value => write!(line, " {key}={value}").unwrap(),
+            }
+        }
+
+        match timestamp {
+            Some(t) if color => format!("{} [{}] {}", t.dim(), self.source, line),
+            Some(t) => format!("{} [{}] {}", t, self.source, line),
+            None => format!("[{}] {}", self.source, line),
         }
     }
 }
@@ -25,18 +81,33 @@ impl LogItem {
 #[cfg(feature = "display")]
 impl std::fmt::Display for LogItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let datetime: chrono::DateTime<chrono::Local> = DateTime::from(self.timestamp);
-
-        write!(
-            f,
-            "{} [{}] {}",
-            datetime
-                .to_rfc3339_opts(chrono::SecondsFormat::Millis, false)
-                .dim(),
-            self.source,
-            self.line,
-        )
+        write!(f, "{}", self.format(LogTimestamps::Local, true))
+    }
+}
+
+/// Remove ANSI escape sequences (such as colors) from a string
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        // CSI sequences are `ESC [`, parameters, and a final byte in the range @ to ~
+        if chars.next_if_eq(&'[').is_some() {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            // other escapes are two characters long
+            chars.next();
+        }
     }
+
+    out
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -45,6 +116,65 @@ pub struct LogsResponse {
     pub logs: Vec<LogItem>,
 }
 
+/// Search the stored logs of a project, so that only the matching lines are downloaded
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct LogSearchRequest {
+    /// Text to look for in the log lines, or a regular expression if `regex` is set
+    pub query: String,
+    pub regex: bool,
+    /// Only search the logs of this deployment, instead of all deployments of the project
+    pub deployment_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Return at most this many lines, the most recent ones
+    pub limit: Option<i32>,
+}
+
+/// Kind of external service that logs are forwarded to
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, strum::Display, strum::EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+#[typeshare::typeshare]
+pub enum LogSinkKind {
+    /// Loki push API
+    Loki,
+    /// Syslog over TCP or TLS
+    Syslog,
+    /// HTTPS endpoint that receives batches of log items as JSON
+    Webhook,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct LogSinkCreateRequest {
+    pub kind: LogSinkKind,
+    pub url: String,
+    /// Headers sent with each batch, e.g. for authentication
+    pub headers: HashMap<String, String>,
+}
+
+/// An external service that the logs of a project are forwarded to.
+/// Logs are sent in batches and retried if the service is unavailable.
+#[derive(Debug, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct LogSinkResponse {
+    pub id: String,
+    pub kind: LogSinkKind,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+    /// The last error when forwarding logs, if the latest batch failed
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct LogSinkListResponse {
+    pub sinks: Vec<LogSinkResponse>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,6 +188,49 @@ mod tests {
         std::env::set_var("TZ", prev_tz);
     }
 
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\x1b[2m2024\x1b[0m \x1b[32m INFO\x1b[0m hello"),
+            "2024  INFO hello"
+        );
+        assert_eq!(strip_ansi("no colors"), "no colors");
+    }
+
+    #[test]
+    fn test_format_options() {
+        let item = LogItem::new(
+            "2024-01-01T12:00:00Z".parse().unwrap(),
+            "app".to_string(),
+            "\x1b[32mINFO\x1b[0m started".to_owned(),
+        );
+
+        assert_eq!(
+            item.format(LogTimestamps::Utc, false),
+            "2024-01-01T12:00:00.000Z [app] INFO started"
+        );
+        assert_eq!(item.format(LogTimestamps::Off, false), "[app] INFO started");
+    }
+
+    #[test]
+    fn test_format_fields() {
+        let mut item = LogItem::new(
+            "2024-01-01T12:00:00Z".parse().unwrap(),
+            "app".to_string(),
+            "request done".to_owned(),
+        );
+        item.fields = serde_json::from_str(r#"{"path": "/users", "status": 200}"#).unwrap();
+
+        assert_eq!(
+            item.format(LogTimestamps::Off, false),
+            "[app] request done path=/users status=200"
+        );
+        assert_eq!(
+            serde_json::to_value(&item).unwrap()["fields"]["status"],
+            200
+        );
+    }
+
     #[test]
     fn test_timezone_formatting() {
         let item = LogItem::new(
@@ -5,5 +5,6 @@ pub mod error;
 pub mod log;
 pub mod project;
 pub mod resource;
+pub mod status;
 pub mod team;
 pub mod user;
@@ -69,6 +69,250 @@ pub struct ProjectListResponse {
     pub projects: Vec<ProjectResponse>,
 }
 
+/// A page of the admin project search
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct AdminProjectSearchResponse {
+    pub projects: Vec<ProjectResponse>,
+    /// Pass as `after` to get the next page. Not set on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Traffic that reached a project's deployments over the last 24 hours
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ProjectStatsResponse {
+    pub requests: i32,
+    pub status_2xx: i32,
+    pub status_3xx: i32,
+    pub status_4xx: i32,
+    pub status_5xx: i32,
+    pub latency_p50_ms: f64,
+    pub latency_p95_ms: f64,
+    pub latency_p99_ms: f64,
+}
+
+impl ProjectStatsResponse {
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = String::new();
+        writeln!(&mut s, "{}", "Requests in the last 24 hours:".bold()).unwrap();
+        writeln!(&mut s, "  Total: {}", self.requests).unwrap();
+        writeln!(&mut s, "  2xx: {}", self.status_2xx.to_string().green()).unwrap();
+        writeln!(&mut s, "  3xx: {}", self.status_3xx.to_string().cyan()).unwrap();
+        writeln!(&mut s, "  4xx: {}", self.status_4xx.to_string().yellow()).unwrap();
+        writeln!(&mut s, "  5xx: {}", self.status_5xx.to_string().red()).unwrap();
+        writeln!(
+            &mut s,
+            "  Latency: p50 {:.0} ms, p95 {:.0} ms, p99 {:.0} ms",
+            self.latency_p50_ms, self.latency_p95_ms, self.latency_p99_ms
+        )
+        .unwrap();
+
+        s
+    }
+}
+
+/// Metered traffic of a project in the current month, and the limits of its plan.
+/// Over the soft limit, requests are answered with 429. Over the hard limit, with 402.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ProjectUsageResponse {
+    pub period_start: DateTime<Utc>,
+    pub requests: i32,
+    pub requests_soft_limit: Option<i32>,
+    pub requests_hard_limit: Option<i32>,
+    /// Outgoing traffic in megabytes
+    pub egress_mb: f64,
+    pub egress_soft_limit_mb: Option<f64>,
+    pub egress_hard_limit_mb: Option<f64>,
+}
+
+impl ProjectUsageResponse {
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        fn line(
+            name: &str,
+            value: f64,
+            soft: Option<f64>,
+            hard: Option<f64>,
+            unit: &str,
+        ) -> String {
+            let value_str = format!("{value:.0}{unit}");
+            let value_str = if hard.is_some_and(|h| value >= h) {
+                value_str.red()
+            } else if soft.is_some_and(|s| value >= s) {
+                value_str.yellow()
+            } else {
+                value_str.green()
+            };
+            let limits = [("soft", soft), ("hard", hard)]
+                .into_iter()
+                .filter_map(|(kind, limit)| limit.map(|l| format!("{kind} limit {l:.0}{unit}")))
+                .collect::<Vec<_>>();
+            if limits.is_empty() {
+                format!("  {name}: {value_str}")
+            } else {
+                format!("  {name}: {value_str} ({})", limits.join(", "))
+            }
+        }
+
+        let mut s = String::new();
+        writeln!(
+            &mut s,
+            "{}",
+            format!("Usage since {}:", self.period_start.format("%Y-%m-%d")).bold()
+        )
+        .unwrap();
+        writeln!(
+            &mut s,
+            "{}",
+            line(
+                "Requests",
+                self.requests as f64,
+                self.requests_soft_limit.map(f64::from),
+                self.requests_hard_limit.map(f64::from),
+                ""
+            )
+        )
+        .unwrap();
+        writeln!(
+            &mut s,
+            "{}",
+            line(
+                "Egress",
+                self.egress_mb,
+                self.egress_soft_limit_mb,
+                self.egress_hard_limit_mb,
+                " MB"
+            )
+        )
+        .unwrap();
+
+        s
+    }
+}
+
+/// A state change of a project's deployment, pushed over the project events websocket
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ProjectEvent {
+    pub project_id: String,
+    pub deployment_id: Option<String>,
+    pub state: DeploymentState,
+    pub timestamp: DateTime<Utc>,
+    pub message: Option<String>,
+}
+
+impl ProjectEvent {
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = format!(
+            "{} {}",
+            self.timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+                .dim(),
+            self.state.to_string_colored()
+        );
+        if let Some(ref id) = self.deployment_id {
+            write!(&mut s, " {}", id.as_str().dark_grey()).unwrap();
+        }
+        if let Some(ref message) = self.message {
+            write!(&mut s, " {message}").unwrap();
+        }
+
+        s
+    }
+}
+
+/// Rules that requests to a project's subdomains must pass before reaching the service
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ProjectAccessRulesRequest {
+    /// IP ranges in CIDR notation that are allowed. Empty allows all IPs.
+    pub allowed_ips: Vec<String>,
+    pub basic_auth: Option<BasicAuthCredentials>,
+    /// Keep the current basic auth credentials when `basic_auth` is not set
+    #[serde(default)]
+    pub keep_basic_auth: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct BasicAuthCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ProjectAccessRulesResponse {
+    pub allowed_ips: Vec<String>,
+    /// Username of the basic auth credentials, if basic auth is required
+    pub basic_auth_username: Option<String>,
+}
+
+impl ProjectAccessRulesResponse {
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = String::new();
+        writeln!(&mut s, "{}", "Access rules:".bold()).unwrap();
+        if self.allowed_ips.is_empty() && self.basic_auth_username.is_none() {
+            writeln!(&mut s, "  None, the project is public").unwrap();
+            return s;
+        }
+        if self.allowed_ips.is_empty() {
+            writeln!(&mut s, "  Allowed IPs: all").unwrap();
+        } else {
+            writeln!(&mut s, "  Allowed IPs:").unwrap();
+            for ip in &self.allowed_ips {
+                writeln!(&mut s, "    - {ip}").unwrap();
+            }
+        }
+        if let Some(ref username) = self.basic_auth_username {
+            writeln!(&mut s, "  Basic auth user: {username}").unwrap();
+        }
+
+        s
+    }
+}
+
+/// Times at which a project is stopped and started again, as cron expressions in UTC
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ProjectSchedule {
+    /// When to stop the current deployment, e.g. `0 22 * * *`
+    pub stop: Option<String>,
+    /// When to start the last deployment again, e.g. `0 7 * * 1-5`
+    pub start: Option<String>,
+}
+
+impl ProjectSchedule {
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = String::new();
+        writeln!(&mut s, "{}", "Schedule (UTC):".bold()).unwrap();
+        if self.stop.is_none() && self.start.is_none() {
+            writeln!(&mut s, "  None, the project runs until stopped").unwrap();
+            return s;
+        }
+        writeln!(
+            &mut s,
+            "  Stop:  {}",
+            self.stop.as_deref().unwrap_or("never")
+        )
+        .unwrap();
+        writeln!(
+            &mut s,
+            "  Start: {}",
+            self.start.as_deref().unwrap_or("never")
+        )
+        .unwrap();
+
+        s
+    }
+}
+
 /// Set wanted field(s) to Some to update those parts of the project
 #[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
 #[typeshare::typeshare]
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -52,41 +55,150 @@ pub struct ResourceListResponse {
     pub resources: Vec<ResourceResponse>,
 }
 
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    Deserialize,
-    Serialize,
-    Eq,
-    PartialEq,
-    strum::AsRefStr,
-    strum::Display,
-    strum::EnumString,
-)]
+/// Storage and connection usage of a database resource
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[typeshare::typeshare]
+pub struct DatabaseUsage {
+    pub r#type: ResourceType,
+    /// Size of the database on disk, in megabytes
+    pub size_mb: f64,
+    /// Number of open connections to the database
+    pub connections: i32,
+    /// Estimated number of rows across all tables
+    pub row_estimate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct DatabaseUsageListResponse {
+    pub usage: Vec<DatabaseUsage>,
+}
+
+/// What happened to a resource in a [`ResourceHistoryEntry`]
+#[derive(Debug, Clone, PartialEq, Eq, strum::Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+#[typeshare::typeshare]
+pub enum ResourceEvent {
+    Created,
+    Updated,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[typeshare::typeshare]
+pub struct ResourceHistoryEntry {
+    pub event: ResourceEvent,
+    /// The user or API key that made the change
+    pub actor: String,
+    pub created_at: DateTime<Utc>,
+    /// Keys in the resource config or output that changed (values are not included)
+    pub changed_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct ResourceHistoryResponse {
+    pub history: Vec<ResourceHistoryEntry>,
+}
+
+/// All resources of a project, as exported for importing them into another project.
+/// The signature is created by the API and checked when the bundle is imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct ResourceExportBundle {
+    /// Project the resources were exported from
+    pub project_id: String,
+    pub created_at: DateTime<Utc>,
+    /// The config and output of each resource
+    pub resources: Vec<ResourceResponse>,
+    pub signature: String,
+}
+
+/// Change the secrets of a project without having to make a new deployment
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[typeshare::typeshare]
+pub struct SecretsUpdateRequest {
+    /// Secrets to add, or overwrite if the key already exists
+    pub set: HashMap<String, String>,
+    /// Keys of secrets to remove
+    pub unset: Vec<String>,
+}
+
+/// Serialized as a string like `database::shared::postgres`, or `custom::<type_id>` for custom resources
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[typeshare::typeshare(serialized_as = "String")]
 // is a flat enum instead of nested enum to allow typeshare
 pub enum ResourceType {
-    #[strum(to_string = "database::shared::postgres")]
-    #[serde(rename = "database::shared::postgres")]
     DatabaseSharedPostgres,
-    #[strum(to_string = "database::aws_rds::postgres")]
-    #[serde(rename = "database::aws_rds::postgres")]
+    DatabaseSharedMySql,
     DatabaseAwsRdsPostgres,
-    #[strum(to_string = "database::aws_rds::mysql")]
-    #[serde(rename = "database::aws_rds::mysql")]
     DatabaseAwsRdsMySql,
-    #[strum(to_string = "database::aws_rds::mariadb")]
-    #[serde(rename = "database::aws_rds::mariadb")]
     DatabaseAwsRdsMariaDB,
+    CacheSharedRedis,
     /// (Will probably be removed)
-    #[strum(to_string = "secrets")]
-    #[serde(rename = "secrets")]
     Secrets,
     /// Local provisioner only
-    #[strum(to_string = "container")]
-    #[serde(rename = "container")]
     Container,
+    /// A resource implemented by a third-party crate, keyed by the plugin's type id so that
+    /// several plugins in one service don't share a resource. See [`crate::CustomResourceInput`]
+    Custom(String),
+}
+
+impl ResourceType {
+    const CUSTOM_PREFIX: &'static str = "custom::";
+}
+
+impl std::fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::DatabaseSharedPostgres => "database::shared::postgres",
+            Self::DatabaseSharedMySql => "database::shared::mysql",
+            Self::DatabaseAwsRdsPostgres => "database::aws_rds::postgres",
+            Self::DatabaseAwsRdsMySql => "database::aws_rds::mysql",
+            Self::DatabaseAwsRdsMariaDB => "database::aws_rds::mariadb",
+            Self::CacheSharedRedis => "cache::shared::redis",
+            Self::Secrets => "secrets",
+            Self::Container => "container",
+            Self::Custom(type_id) => return write!(f, "{}{type_id}", Self::CUSTOM_PREFIX),
+        };
+        f.write_str(s)
+    }
+}
+
+impl std::str::FromStr for ResourceType {
+    type Err = strum::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "database::shared::postgres" => Self::DatabaseSharedPostgres,
+            "database::shared::mysql" => Self::DatabaseSharedMySql,
+            "database::aws_rds::postgres" => Self::DatabaseAwsRdsPostgres,
+            "database::aws_rds::mysql" => Self::DatabaseAwsRdsMySql,
+            "database::aws_rds::mariadb" => Self::DatabaseAwsRdsMariaDB,
+            "cache::shared::redis" => Self::CacheSharedRedis,
+            "secrets" => Self::Secrets,
+            "container" => Self::Container,
+            _ => match s.strip_prefix(Self::CUSTOM_PREFIX) {
+                Some(type_id) if !type_id.is_empty() => Self::Custom(type_id.to_owned()),
+                _ => return Err(strum::ParseError::VariantNotFound),
+            },
+        })
+    }
+}
+
+impl Serialize for ResourceType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResourceType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| serde::de::Error::custom(format!("unknown resource type `{s}`")))
+    }
 }
 
 #[cfg(test)]
@@ -99,13 +211,22 @@ mod test {
     fn to_string_and_back() {
         let inputs = [
             ResourceType::DatabaseSharedPostgres,
+            ResourceType::DatabaseSharedMySql,
+            ResourceType::CacheSharedRedis,
             ResourceType::Secrets,
             ResourceType::Container,
+            ResourceType::Custom("meilisearch".to_owned()),
         ];
 
         for input in inputs {
-            let actual = ResourceType::from_str(input.as_ref()).unwrap();
+            let actual = ResourceType::from_str(&input.to_string()).unwrap();
             assert_eq!(input, actual, ":{} should map back to itself", input);
+            let json = serde_json::to_string(&input).unwrap();
+            assert_eq!(json, format!("\"{input}\""));
+            assert_eq!(input, serde_json::from_str(&json).unwrap());
         }
+
+        assert!(ResourceType::from_str("custom::").is_err());
+        assert!(ResourceType::from_str("custom").is_err());
     }
 }
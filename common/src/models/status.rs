@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+#[cfg(feature = "display")]
+use crossterm::style::Stylize;
+#[cfg(feature = "display")]
+use std::fmt::Write;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Display, Serialize, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+#[strum(ascii_case_insensitive)]
+#[typeshare::typeshare]
+pub enum HealthState {
+    Healthy,
+    Degraded,
+    Unhealthy,
+    /// Fallback
+    #[serde(other)]
+    Unknown,
+}
+
+impl HealthState {
+    #[cfg(feature = "display")]
+    pub fn get_color_crossterm(&self) -> crossterm::style::Color {
+        use crossterm::style::Color;
+
+        match self {
+            Self::Healthy => Color::Green,
+            Self::Degraded => Color::Yellow,
+            Self::Unhealthy => Color::Red,
+            Self::Unknown => Color::Grey,
+        }
+    }
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        self.to_string()
+            .with(self.get_color_crossterm())
+            .to_string()
+    }
+}
+
+/// Health of the platform, from the `/status` endpoint
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct PlatformStatusResponse {
+    /// The worst state of all components
+    pub state: HealthState,
+    pub components: Vec<ComponentStatus>,
+}
+
+/// Health of one part of the platform, such as docker, the worker queue, auth or the database
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub struct ComponentStatus {
+    pub name: String,
+    pub state: HealthState,
+    /// Details about a degraded or unhealthy component, e.g. how full the worker queue is
+    pub message: Option<String>,
+}
+
+impl PlatformStatusResponse {
+    pub fn is_healthy(&self) -> bool {
+        self.state == HealthState::Healthy
+    }
+
+    /// Banner listing the components that are not healthy
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = String::new();
+        writeln!(
+            &mut s,
+            "{} {}",
+            "Shuttle platform status:".bold(),
+            self.state.to_string_colored()
+        )
+        .unwrap();
+        for component in self
+            .components
+            .iter()
+            .filter(|c| c.state != HealthState::Healthy)
+        {
+            write!(
+                &mut s,
+                "  {}: {}",
+                component.name,
+                component.state.to_string_colored()
+            )
+            .unwrap();
+            if let Some(message) = &component.message {
+                write!(&mut s, " ({message})").unwrap();
+            }
+            writeln!(&mut s).unwrap();
+        }
+        writeln!(&mut s, "The error above might be caused by this.").unwrap();
+
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_health_state() {
+        let status: PlatformStatusResponse = serde_json::from_str(
+            r#"{"state":"degraded","components":[{"name":"docker","state":"healthy","message":null},{"name":"worker queue","state":"overloaded","message":"95% full"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(status.state, HealthState::Degraded);
+        assert_eq!(status.components[1].state, HealthState::Unknown);
+        assert!(!status.is_healthy());
+    }
+}
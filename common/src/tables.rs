@@ -8,11 +8,12 @@ use crate::{
     models::{
         certificate::CertificateResponse,
         deployment::DeploymentResponse,
+        log::LogSinkResponse,
         project::ProjectResponse,
-        resource::{ResourceResponse, ResourceType},
+        resource::{DatabaseUsage, ResourceHistoryEntry, ResourceResponse, ResourceType},
     },
     secrets::SecretStore,
-    DatabaseInfo,
+    CustomResourceInput, DatabaseInfo,
 };
 
 pub fn get_certificates_table(certs: &[CertificateResponse], raw: bool) -> String {
@@ -20,13 +21,64 @@ pub fn get_certificates_table(certs: &[CertificateResponse], raw: bool) -> Strin
     table
         .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
         .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Certificate ID", "Subject", "Expires"]);
+        .set_header(vec![
+            "Certificate ID",
+            "Subject",
+            "Expires",
+            "HSTS",
+            "HTTP paths",
+        ]);
 
     for cert in certs {
         table.add_row(vec![
             Cell::new(&cert.id).add_attribute(Attribute::Bold),
             Cell::new(&cert.subject),
             Cell::new(&cert.not_after),
+            Cell::new(if cert.hsts { "on" } else { "off" }),
+            Cell::new(cert.http_paths.join(", ")),
+        ]);
+    }
+
+    table.to_string()
+}
+
+pub fn get_log_sinks_table(sinks: &[LogSinkResponse], raw: bool) -> String {
+    let mut table = Table::new();
+    table
+        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
+        .set_content_arrangement(ContentArrangement::Disabled)
+        .set_header(vec!["Sink ID", "Kind", "URL", "Status"]);
+
+    for sink in sinks {
+        let (status, color) = match sink.last_error {
+            Some(ref e) => (e.as_str(), Color::Red),
+            None => ("ok", Color::Green),
+        };
+        table.add_row(vec![
+            Cell::new(&sink.id).add_attribute(Attribute::Bold),
+            Cell::new(sink.kind),
+            Cell::new(&sink.url),
+            Cell::new(status).fg(color),
+        ]);
+    }
+
+    table.to_string()
+}
+
+pub fn get_resource_history_table(history: &[ResourceHistoryEntry], raw: bool) -> String {
+    let mut table = Table::new();
+    table
+        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
+        .set_content_arrangement(ContentArrangement::Disabled)
+        .set_header(vec!["Date", "Event", "By", "Changed"]);
+
+    for entry in history {
+        let datetime: DateTime<Local> = DateTime::from(entry.created_at);
+        table.add_row(vec![
+            Cell::new(datetime.to_rfc3339_opts(SecondsFormat::Secs, false)),
+            Cell::new(&entry.event).add_attribute(Attribute::Bold),
+            Cell::new(&entry.actor),
+            Cell::new(entry.changed_keys.join(", ")),
         ]);
     }
 
@@ -89,6 +141,7 @@ pub fn get_projects_table(projects: &[ProjectResponse], raw: bool) -> String {
 
 pub fn get_resource_tables(
     resources: &[ResourceResponse],
+    usage: &[DatabaseUsage],
     service_name: &str,
     raw: bool,
     show_secrets: bool,
@@ -113,22 +166,60 @@ pub fn get_resource_tables(
                 matches!(
                     r.r#type,
                     ResourceType::DatabaseSharedPostgres
+                        | ResourceType::DatabaseSharedMySql
                         | ResourceType::DatabaseAwsRdsMariaDB
                         | ResourceType::DatabaseAwsRdsMySql
                         | ResourceType::DatabaseAwsRdsPostgres
+                        | ResourceType::CacheSharedRedis
                 )
             })
             .map(Clone::clone)
             .collect::<Vec<_>>(),
+        usage,
         service_name,
         raw,
         show_secrets,
     ));
+    output.push(get_custom_resources_table(
+        &resources
+            .iter()
+            .filter(|r| matches!(r.r#type, ResourceType::Custom(_)))
+            .map(Clone::clone)
+            .collect::<Vec<_>>(),
+        service_name,
+        raw,
+    ));
     output.join("\n")
 }
 
+fn get_custom_resources_table(
+    resources: &[ResourceResponse],
+    service_name: &str,
+    raw: bool,
+) -> String {
+    if resources.is_empty() {
+        return String::new();
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
+        .set_content_arrangement(ContentArrangement::Disabled)
+        .set_header(vec!["Type", "Plugin"]);
+
+    for resource in resources {
+        let type_id = serde_json::from_value::<CustomResourceInput>(resource.config.clone())
+            .map(|c| c.type_id)
+            .unwrap_or_else(|_| "N/A".to_owned());
+        table.add_row(vec![resource.r#type.to_string(), type_id]);
+    }
+
+    format!("These custom resources are linked to {service_name}\n{table}")
+}
+
 fn get_databases_table(
     databases: &[ResourceResponse],
+    usage: &[DatabaseUsage],
     service_name: &str,
     raw: bool,
     show_secrets: bool,
@@ -140,15 +231,34 @@ fn get_databases_table(
     let mut table = Table::new();
     table
         .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
-        .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Type", "Connection string"]);
+        .set_content_arrangement(ContentArrangement::Disabled);
+    if usage.is_empty() {
+        table.set_header(vec!["Type", "Connection string"]);
+    } else {
+        table.set_header(vec!["Type", "Connection string", "Usage"]);
+    }
 
     for database in databases {
         let connection_string = serde_json::from_value::<DatabaseInfo>(database.output.clone())
             .expect("resource data to be a valid database")
             .connection_string(show_secrets);
 
-        table.add_row(vec![database.r#type.to_string(), connection_string]);
+        let mut row = vec![database.r#type.to_string(), connection_string];
+        if !usage.is_empty() {
+            row.push(
+                usage
+                    .iter()
+                    .find(|u| u.r#type == database.r#type)
+                    .map(|u| {
+                        format!(
+                            "{:.1} MB, {} connections, ~{:.0} rows",
+                            u.size_mb, u.connections, u.row_estimate
+                        )
+                    })
+                    .unwrap_or_else(|| "N/A".to_owned()),
+            );
+        }
+        table.add_row(row);
     }
 
     let show_secret_hint = if databases.is_empty() || show_secrets {
@@ -160,7 +270,7 @@ fn get_databases_table(
     format!("These databases are linked to {service_name}\n{table}\n{show_secret_hint}")
 }
 
-fn get_secrets_table(secrets: &[ResourceResponse], service_name: &str, raw: bool) -> String {
+pub fn get_secrets_table(secrets: &[ResourceResponse], service_name: &str, raw: bool) -> String {
     let Some(secrets) = secrets.first() else {
         return String::new();
     };
@@ -22,6 +22,54 @@ const MIN_CONNECTIONS: u32 = 1;
 #[allow(dead_code)]
 const MAX_CONNECTIONS: u32 = 5;
 
+/// Instance classes that can be requested
+pub const INSTANCE_CLASSES: &[&str] = &[
+    "db.t4g.micro",
+    "db.t4g.small",
+    "db.t4g.medium",
+    "db.t4g.large",
+];
+/// Range of storage sizes that can be requested, in GB
+pub const ALLOCATED_STORAGE: std::ops::RangeInclusive<u32> = 20..=1000;
+
+/// Check the requested instance settings against what can be provisioned
+#[allow(dead_code)]
+fn validate(input: &DbInput, engine_versions: &[&str]) -> Result<(), Error> {
+    if let Some(ref class) = input.instance_class {
+        if !INSTANCE_CLASSES.contains(&class.as_str()) {
+            return Err(Error::Custom(shuttle_service::error::CustomError::msg(
+                format!(
+                    "unsupported instance class '{class}', expected one of: {}",
+                    INSTANCE_CLASSES.join(", ")
+                ),
+            )));
+        }
+    }
+    if let Some(storage) = input.allocated_storage {
+        if !ALLOCATED_STORAGE.contains(&storage) {
+            return Err(Error::Custom(shuttle_service::error::CustomError::msg(
+                format!(
+                    "unsupported allocated storage of {storage} GB, expected {} to {} GB",
+                    ALLOCATED_STORAGE.start(),
+                    ALLOCATED_STORAGE.end()
+                ),
+            )));
+        }
+    }
+    if let Some(ref version) = input.engine_version {
+        if !engine_versions.contains(&version.as_str()) {
+            return Err(Error::Custom(shuttle_service::error::CustomError::msg(
+                format!(
+                    "unsupported engine version '{version}', expected one of: {}",
+                    engine_versions.join(", ")
+                ),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Conditionally request a Shuttle resource
 #[derive(Serialize, Deserialize)]
 #[serde(untagged)]
@@ -31,7 +79,7 @@ pub enum MaybeRequest {
 }
 
 macro_rules! aws_engine {
-    ($feature:expr, $struct_ident:ident, $res_type:ident) => {
+    ($feature:expr, $struct_ident:ident, $res_type:ident, [$($version:literal),+]) => {
         paste::paste! {
             #[cfg(feature = $feature)]
             #[derive(Default)]
@@ -53,6 +101,27 @@ macro_rules! aws_engine {
 
                     self
                 }
+
+                /// Use a bigger instance class than the default `db.t4g.micro`. See [`INSTANCE_CLASSES`].
+                pub fn instance_class(mut self, instance_class: &str) -> Self {
+                    self.0.instance_class = Some(instance_class.to_string());
+
+                    self
+                }
+
+                /// Allocate more storage than the default 20 GB. See [`ALLOCATED_STORAGE`].
+                pub fn allocated_storage(mut self, gigabytes: u32) -> Self {
+                    self.0.allocated_storage = Some(gigabytes);
+
+                    self
+                }
+
+                /// Pin the engine version. See the README for the versions of each engine.
+                pub fn engine_version(mut self, engine_version: &str) -> Self {
+                    self.0.engine_version = Some(engine_version.to_string());
+
+                    self
+                }
             }
 
             #[cfg(feature = $feature)]
@@ -62,6 +131,7 @@ macro_rules! aws_engine {
                 type Output = OutputWrapper;
 
                 async fn build(self, factory: &ResourceFactory) -> Result<Self::Input, Error> {
+                    validate(&self.0, &[$($version),+])?;
                     let md = factory.get_metadata();
                     Ok(match md.env {
                         Environment::Deployment => MaybeRequest::Request(ProvisionResourceRequest {
@@ -84,9 +154,9 @@ macro_rules! aws_engine {
     };
 }
 
-aws_engine!("postgres", Postgres, DatabaseAwsRdsPostgres);
-aws_engine!("mysql", MySql, DatabaseAwsRdsMySql);
-aws_engine!("mariadb", MariaDB, DatabaseAwsRdsMariaDB);
+aws_engine!("postgres", Postgres, DatabaseAwsRdsPostgres, ["14", "15", "16"]);
+aws_engine!("mysql", MySql, DatabaseAwsRdsMySql, ["8.0"]);
+aws_engine!("mariadb", MariaDB, DatabaseAwsRdsMariaDB, ["10.6", "10.11"]);
 
 #[derive(Serialize, Deserialize)]
 #[serde(transparent)]
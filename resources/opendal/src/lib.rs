@@ -6,18 +6,20 @@ use opendal::{Operator, Scheme};
 use serde::{Deserialize, Serialize};
 use shuttle_service::{
     error::{CustomError, Error as ShuttleError},
-    IntoResource, ResourceFactory, ResourceInputBuilder,
+    Environment, IntoResource, ResourceFactory, ResourceInputBuilder,
 };
 
 #[derive(Serialize)]
 pub struct Opendal {
     scheme: String,
+    local_scheme: Option<String>,
 }
 
 impl Default for Opendal {
     fn default() -> Self {
         Self {
             scheme: "memory".to_string(),
+            local_scheme: None,
         }
     }
 }
@@ -27,6 +29,12 @@ impl Opendal {
         self.scheme = scheme.to_string();
         self
     }
+
+    /// Use this scheme instead when running locally, e.g. `"fs"` to keep files on disk during development
+    pub fn local_scheme(mut self, local_scheme: &str) -> Self {
+        self.local_scheme = Some(local_scheme.to_string());
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -50,14 +58,28 @@ impl ResourceInputBuilder for Opendal {
     type Output = OpendalOutput;
 
     async fn build(self, factory: &ResourceFactory) -> Result<Self::Input, ShuttleError> {
-        Ok(OpendalOutput {
-            scheme: self.scheme,
-            cfg: factory
-                .get_secrets()
-                .into_iter()
-                .map(|(k, v)| (k, v.expose().clone()))
-                .collect(),
-        })
+        let md = factory.get_metadata();
+        let mut cfg: HashMap<String, String> = factory
+            .get_secrets()
+            .into_iter()
+            .map(|(k, v)| (k, v.expose().clone()))
+            .collect();
+
+        let scheme = match (md.env, self.local_scheme) {
+            (Environment::Local, Some(local_scheme)) => {
+                // Default to a folder in the local storage so that files persist between runs
+                if local_scheme == "fs" && !cfg.contains_key("root") {
+                    let root = std::env::current_dir()
+                        .map(|cd| cd.join(md.storage_path).join("opendal"))
+                        .map_err(|e| ShuttleError::Custom(CustomError::new(e)))?;
+                    cfg.insert("root".to_string(), root.display().to_string());
+                }
+                local_scheme
+            }
+            _ => self.scheme,
+        };
+
+        Ok(OpendalOutput { scheme, cfg })
     }
 }
 
@@ -94,6 +116,28 @@ mod test {
         assert_eq!(op.info().scheme(), Scheme::Fs)
     }
 
+    #[tokio::test]
+    async fn opendal_local_scheme() {
+        let factory =
+            ResourceFactory::new(Default::default(), Default::default(), Environment::Local);
+
+        let odal = Opendal::default().scheme("s3").local_scheme("fs");
+        let output = odal.build(&factory).await.unwrap();
+        assert_eq!(output.scheme, "fs");
+        assert!(output.cfg.get("root").unwrap().ends_with("opendal"));
+
+        let factory = ResourceFactory::new(
+            Default::default(),
+            Default::default(),
+            Environment::Deployment,
+        );
+
+        let odal = Opendal::default().scheme("s3").local_scheme("fs");
+        let output = odal.build(&factory).await.unwrap();
+        assert_eq!(output.scheme, "s3");
+        assert!(!output.cfg.contains_key("root"));
+    }
+
     #[tokio::test]
     async fn opendal_s3() {
         let factory = ResourceFactory::new(
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shuttle_service::{
+    resource::{ProvisionResourceRequest, ResourceType},
+    DatabaseResource, DbInput, Environment, Error, IntoResource, ResourceFactory,
+    ResourceInputBuilder,
+};
+
+#[allow(dead_code)]
+const MIN_CONNECTIONS: u32 = 1;
+#[allow(dead_code)]
+const MAX_CONNECTIONS: u32 = 5;
+
+/// Shuttle managed MySQL DB in a shared cluster
+#[derive(Default)]
+pub struct MySql(DbInput);
+
+impl MySql {
+    /// Use a custom connection string for local runs
+    pub fn local_uri(mut self, local_uri: &str) -> Self {
+        self.0.local_uri = Some(local_uri.to_string());
+
+        self
+    }
+}
+
+/// Conditionally request a Shuttle resource
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MaybeRequest {
+    Request(ProvisionResourceRequest),
+    NotRequest(DatabaseResource),
+}
+
+#[async_trait]
+impl ResourceInputBuilder for MySql {
+    type Input = MaybeRequest;
+    type Output = OutputWrapper;
+
+    async fn build(self, factory: &ResourceFactory) -> Result<Self::Input, Error> {
+        let md = factory.get_metadata();
+        Ok(match md.env {
+            Environment::Deployment => MaybeRequest::Request(ProvisionResourceRequest {
+                r#type: ResourceType::DatabaseSharedMySql,
+                config: serde_json::to_value(self.0).unwrap(),
+            }),
+            Environment::Local => match self.0.local_uri {
+                Some(local_uri) => {
+                    MaybeRequest::NotRequest(DatabaseResource::ConnectionString(local_uri))
+                }
+                None => MaybeRequest::Request(ProvisionResourceRequest {
+                    r#type: ResourceType::DatabaseSharedMySql,
+                    config: serde_json::to_value(self.0).unwrap(),
+                }),
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OutputWrapper(DatabaseResource);
+
+#[async_trait]
+impl IntoResource<String> for OutputWrapper {
+    async fn into_resource(self) -> Result<String, Error> {
+        Ok(match self.0 {
+            DatabaseResource::ConnectionString(s) => s,
+            DatabaseResource::Info(info) => info.connection_string(true),
+        })
+    }
+}
+
+#[cfg(feature = "diesel-async")]
+#[async_trait]
+impl IntoResource<diesel_async::AsyncMysqlConnection> for OutputWrapper {
+    async fn into_resource(self) -> Result<diesel_async::AsyncMysqlConnection, Error> {
+        use diesel_async::{AsyncConnection, AsyncMysqlConnection};
+
+        let connection_string: String = self.into_resource().await?;
+
+        Ok(AsyncMysqlConnection::establish(&connection_string)
+            .await
+            .map_err(shuttle_service::error::CustomError::new)?)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+#[async_trait]
+impl IntoResource<sqlx::MySqlPool> for OutputWrapper {
+    async fn into_resource(self) -> Result<sqlx::MySqlPool, Error> {
+        let connection_string: String = self.into_resource().await?;
+
+        Ok(sqlx::mysql::MySqlPoolOptions::new()
+            .min_connections(MIN_CONNECTIONS)
+            .max_connections(MAX_CONNECTIONS)
+            .connect(&connection_string)
+            .await
+            .map_err(shuttle_service::error::CustomError::new)?)
+    }
+}
@@ -22,12 +22,25 @@ const MAX_CONNECTIONS: u32 = 5;
 
 /// Shuttle managed Postgres DB in a shared cluster
 #[derive(Default)]
-pub struct Postgres(DbInput);
+pub struct Postgres {
+    input: DbInput,
+    #[cfg(feature = "sqlx")]
+    migrations: Option<String>,
+}
 
 impl Postgres {
     /// Use a custom connection string for local runs
     pub fn local_uri(mut self, local_uri: &str) -> Self {
-        self.0.local_uri = Some(local_uri.to_string());
+        self.input.local_uri = Some(local_uri.to_string());
+
+        self
+    }
+
+    /// Run the sqlx migrations in this directory before the `sqlx::PgPool` is handed to the service.
+    /// An advisory lock is held while migrating, so concurrent deployments don't race each other.
+    #[cfg(feature = "sqlx")]
+    pub fn migrations(mut self, path: &str) -> Self {
+        self.migrations = Some(path.to_string());
 
         self
     }
@@ -37,8 +50,25 @@ impl Postgres {
 #[derive(Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum MaybeRequest {
-    Request(ProvisionResourceRequest),
-    NotRequest(DatabaseResource),
+    Request {
+        #[serde(flatten)]
+        request: ProvisionResourceRequest,
+        #[serde(flatten)]
+        state: State,
+    },
+    NotRequest {
+        output: DatabaseResource,
+        #[serde(flatten)]
+        state: State,
+    },
+}
+
+/// Settings used when turning the provisioned database into a resource.
+/// The runtime hands them back next to the provisioned output.
+#[derive(Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    migrations: Option<String>,
 }
 
 #[async_trait]
@@ -47,33 +77,63 @@ impl ResourceInputBuilder for Postgres {
     type Output = OutputWrapper;
 
     async fn build(self, factory: &ResourceFactory) -> Result<Self::Input, Error> {
+        let state = State {
+            #[cfg(feature = "sqlx")]
+            migrations: self.migrations,
+            #[cfg(not(feature = "sqlx"))]
+            migrations: None,
+        };
+
         let md = factory.get_metadata();
         Ok(match md.env {
-            Environment::Deployment => MaybeRequest::Request(ProvisionResourceRequest {
-                r#type: ResourceType::DatabaseSharedPostgres,
-                config: serde_json::to_value(self.0).unwrap(),
-            }),
-            Environment::Local => match self.0.local_uri {
-                Some(local_uri) => {
-                    MaybeRequest::NotRequest(DatabaseResource::ConnectionString(local_uri))
-                }
-                None => MaybeRequest::Request(ProvisionResourceRequest {
+            Environment::Deployment => MaybeRequest::Request {
+                request: ProvisionResourceRequest {
                     r#type: ResourceType::DatabaseSharedPostgres,
-                    config: serde_json::to_value(self.0).unwrap(),
-                }),
+                    config: serde_json::to_value(self.input).unwrap(),
+                },
+                state,
+            },
+            Environment::Local => match self.input.local_uri {
+                Some(local_uri) => MaybeRequest::NotRequest {
+                    output: DatabaseResource::ConnectionString(local_uri),
+                    state,
+                },
+                None => MaybeRequest::Request {
+                    request: ProvisionResourceRequest {
+                        r#type: ResourceType::DatabaseSharedPostgres,
+                        config: serde_json::to_value(self.input).unwrap(),
+                    },
+                    state,
+                },
             },
         })
     }
 }
 
 #[derive(Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct OutputWrapper(DatabaseResource);
+#[serde(untagged)]
+pub enum OutputWrapper {
+    WithState {
+        output: DatabaseResource,
+        #[serde(flatten)]
+        state: State,
+    },
+    Plain(DatabaseResource),
+}
+
+impl OutputWrapper {
+    fn into_parts(self) -> (DatabaseResource, State) {
+        match self {
+            Self::WithState { output, state } => (output, state),
+            Self::Plain(output) => (output, State::default()),
+        }
+    }
+}
 
 #[async_trait]
 impl IntoResource<String> for OutputWrapper {
     async fn into_resource(self) -> Result<String, Error> {
-        Ok(match self.0 {
+        Ok(match self.into_parts().0 {
             DatabaseResource::ConnectionString(s) => s,
             DatabaseResource::Info(info) => info.connection_string(true),
         })
@@ -132,14 +192,27 @@ impl IntoResource<diesel_deadpool::Pool<diesel_async::AsyncPgConnection>> for Ou
 #[async_trait]
 impl IntoResource<sqlx::PgPool> for OutputWrapper {
     async fn into_resource(self) -> Result<sqlx::PgPool, Error> {
-        let connection_string: String = self.into_resource().await?;
+        let (output, state) = self.into_parts();
+        let connection_string: String = Self::Plain(output).into_resource().await?;
 
-        Ok(sqlx::postgres::PgPoolOptions::new()
+        let pool = sqlx::postgres::PgPoolOptions::new()
             .min_connections(MIN_CONNECTIONS)
             .max_connections(MAX_CONNECTIONS)
             .connect(&connection_string)
             .await
-            .map_err(shuttle_service::error::CustomError::new)?)
+            .map_err(shuttle_service::error::CustomError::new)?;
+
+        if let Some(migrations) = state.migrations {
+            // The migrator takes a Postgres advisory lock while running
+            sqlx::migrate::Migrator::new(std::path::Path::new(&migrations))
+                .await
+                .map_err(shuttle_service::error::CustomError::new)?
+                .run(&pool)
+                .await
+                .map_err(shuttle_service::error::CustomError::new)?;
+        }
+
+        Ok(pool)
     }
 }
 
@@ -214,3 +287,44 @@ impl IntoResource<SerdeJsonOperator> for OutputWrapper {
         Ok(SerdeJsonOperator(self.into_resource().await?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_keeps_migrations() {
+        let output: OutputWrapper = serde_json::from_value(serde_json::json!({
+            "output": "postgres://localhost",
+            "migrations": "./migrations",
+        }))
+        .unwrap();
+        let (output, state) = output.into_parts();
+        assert!(
+            matches!(output, DatabaseResource::ConnectionString(s) if s == "postgres://localhost")
+        );
+        assert_eq!(state.migrations.as_deref(), Some("./migrations"));
+
+        let output: OutputWrapper =
+            serde_json::from_value(serde_json::json!("postgres://localhost")).unwrap();
+        assert!(output.into_parts().1.migrations.is_none());
+    }
+
+    #[test]
+    fn local_uri_input_is_a_custom_resource() {
+        let input = MaybeRequest::NotRequest {
+            output: DatabaseResource::ConnectionString("postgres://localhost".to_owned()),
+            state: State {
+                migrations: Some("./migrations".to_owned()),
+            },
+        };
+        let value = serde_json::to_value(&input).unwrap();
+        assert!(serde_json::from_value::<ProvisionResourceRequest>(value.clone()).is_err());
+
+        let output: OutputWrapper = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            output.into_parts().1.migrations.as_deref(),
+            Some("./migrations")
+        );
+    }
+}
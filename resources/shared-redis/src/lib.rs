@@ -0,0 +1,92 @@
+#![doc = include_str!("../README.md")]
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shuttle_service::{
+    resource::{ProvisionResourceRequest, ResourceType},
+    DatabaseResource, DbInput, Environment, Error, IntoResource, ResourceFactory,
+    ResourceInputBuilder,
+};
+
+/// Shuttle managed Redis instance in a shared cluster
+#[derive(Default)]
+pub struct Redis(DbInput);
+
+impl Redis {
+    /// Use a custom connection string for local runs
+    pub fn local_uri(mut self, local_uri: &str) -> Self {
+        self.0.local_uri = Some(local_uri.to_string());
+
+        self
+    }
+}
+
+/// Conditionally request a Shuttle resource
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MaybeRequest {
+    Request(ProvisionResourceRequest),
+    NotRequest(DatabaseResource),
+}
+
+#[async_trait]
+impl ResourceInputBuilder for Redis {
+    type Input = MaybeRequest;
+    type Output = OutputWrapper;
+
+    async fn build(self, factory: &ResourceFactory) -> Result<Self::Input, Error> {
+        let md = factory.get_metadata();
+        Ok(match md.env {
+            Environment::Deployment => MaybeRequest::Request(ProvisionResourceRequest {
+                r#type: ResourceType::CacheSharedRedis,
+                config: serde_json::to_value(self.0).unwrap(),
+            }),
+            Environment::Local => match self.0.local_uri {
+                Some(local_uri) => {
+                    MaybeRequest::NotRequest(DatabaseResource::ConnectionString(local_uri))
+                }
+                None => MaybeRequest::Request(ProvisionResourceRequest {
+                    r#type: ResourceType::CacheSharedRedis,
+                    config: serde_json::to_value(self.0).unwrap(),
+                }),
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OutputWrapper(DatabaseResource);
+
+#[async_trait]
+impl IntoResource<String> for OutputWrapper {
+    async fn into_resource(self) -> Result<String, Error> {
+        Ok(match self.0 {
+            DatabaseResource::ConnectionString(s) => s,
+            DatabaseResource::Info(info) => info.connection_string(true),
+        })
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl IntoResource<redis::Client> for OutputWrapper {
+    async fn into_resource(self) -> Result<redis::Client, Error> {
+        let connection_string: String = self.into_resource().await?;
+
+        Ok(redis::Client::open(connection_string)
+            .map_err(shuttle_service::error::CustomError::new)?)
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl IntoResource<redis::aio::ConnectionManager> for OutputWrapper {
+    async fn into_resource(self) -> Result<redis::aio::ConnectionManager, Error> {
+        let client: redis::Client = self.into_resource().await?;
+
+        Ok(redis::aio::ConnectionManager::new(client)
+            .await
+            .map_err(shuttle_service::error::CustomError::new)?)
+    }
+}
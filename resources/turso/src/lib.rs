@@ -1,3 +1,5 @@
+use std::{path::PathBuf, time::Duration};
+
 use async_trait::async_trait;
 use libsql::{Builder, Database};
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,7 @@ pub struct Turso {
     addr: String,
     token: String,
     local_addr: Option<String>,
+    sync_interval: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -19,6 +22,15 @@ pub struct TursoOutput {
     conn_url: Url,
     token: Option<String>,
     remote: bool,
+    /// Keep an embedded replica of the remote database in this file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replica: Option<TursoReplica>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TursoReplica {
+    path: PathBuf,
+    sync_interval: Duration,
 }
 
 impl Turso {
@@ -36,6 +48,12 @@ impl Turso {
         self.local_addr = Some(local_addr.to_string());
         self
     }
+
+    /// Read from an embedded replica of the remote database, synced every `secs` seconds
+    pub fn sync_interval(mut self, secs: u64) -> Self {
+        self.sync_interval = Some(secs);
+        self
+    }
 }
 
 pub enum Error {
@@ -59,7 +77,9 @@ impl Turso {
         &self,
         addr: &str,
         remote: bool,
+        factory: &ResourceFactory,
     ) -> Result<TursoOutput, shuttle_service::Error> {
+        let md = factory.get_metadata();
         Ok(TursoOutput {
             conn_url: Url::parse(addr).map_err(Error::UrlParseError)?,
             token: if self.token.is_empty() {
@@ -68,6 +88,12 @@ impl Turso {
                 Some(self.token.clone())
             },
             remote,
+            replica: self.sync_interval.map(|secs| TursoReplica {
+                path: md
+                    .storage_path
+                    .join(format!("{}-replica.db", md.project_name)),
+                sync_interval: Duration::from_secs(secs),
+            }),
         })
     }
 }
@@ -89,12 +115,12 @@ impl ResourceInputBuilder for Turso {
                             "addr must start with either libsql:// or https://",
                         )));
                     }
-                    self.output_from_addr(&self.addr, true).await
+                    self.output_from_addr(&self.addr, true, factory).await
                 }
             }
             Environment::Local => {
                 match self.local_addr {
-                    Some(ref local_addr) => self.output_from_addr(local_addr, true).await,
+                    Some(ref local_addr) => self.output_from_addr(local_addr, true, factory).await,
                     None => {
                         // Default to a local db of the name of the service.
                         let db_file = std::env::current_dir() // Should be root of the project's workspace
@@ -111,6 +137,7 @@ impl ResourceInputBuilder for Turso {
                             // Nullify the token since we're using a file as database.
                             token: None,
                             remote: false,
+                            replica: None,
                         })
                     }
                 }
@@ -122,6 +149,30 @@ impl ResourceInputBuilder for Turso {
 #[async_trait]
 impl IntoResource<Database> for TursoOutput {
     async fn into_resource(self) -> Result<Database, shuttle_service::Error> {
+        if let Some(replica) = self.replica {
+            if let Some(parent) = replica.path.parent() {
+                std::fs::create_dir_all(parent).map_err(Error::LocateLocalDB)?;
+            }
+            let database = Builder::new_remote_replica(
+                replica.path,
+                self.conn_url.to_string(),
+                self.token.ok_or(ShuttleError::Custom(CustomError::msg(
+                    "missing token for remote database",
+                )))?,
+            )
+            .sync_interval(replica.sync_interval)
+            .build()
+            .await
+            .map_err(|err| ShuttleError::Custom(err.into()))?;
+            // Get up to date before the service starts reading from the replica
+            database
+                .sync()
+                .await
+                .map_err(|err| ShuttleError::Custom(err.into()))?;
+
+            return Ok(database);
+        }
+
         let database = if self.remote {
             Builder::new_remote(
                 self.conn_url.to_string(),
@@ -161,6 +212,7 @@ mod test {
                 conn_url: Url::parse(local_addr).unwrap(),
                 token: None,
                 remote: true,
+                replica: None,
             }
         )
     }
@@ -198,6 +250,36 @@ mod test {
                 conn_url: Url::parse(&addr).unwrap(),
                 token: Some("token".to_string()),
                 remote: true,
+                replica: None,
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn remote_database_with_replica() {
+        let factory = ResourceFactory::new(
+            "my-project".to_string(),
+            Default::default(),
+            Environment::Deployment,
+        );
+
+        let addr = "libsql://my-turso-addr.turso.io";
+        let turso = Turso::default().addr(addr).token("token").sync_interval(30);
+        let output = turso.build(&factory).await.unwrap();
+
+        assert_eq!(
+            output,
+            TursoOutput {
+                conn_url: Url::parse(addr).unwrap(),
+                token: Some("token".to_string()),
+                remote: true,
+                replica: Some(TursoReplica {
+                    path: factory
+                        .get_metadata()
+                        .storage_path
+                        .join("my-project-replica.db"),
+                    sync_interval: Duration::from_secs(30),
+                }),
             }
         )
     }
@@ -9,6 +9,8 @@ mod plugins;
 /// shuttle.dev runtime
 mod rt;
 mod start;
+/// Background workers next to a service
+mod workers;
 
 #[cfg(feature = "setup-otel-exporter")]
 mod telemetry;
@@ -16,13 +18,14 @@ mod telemetry;
 // Public API
 // Useful re-exports
 pub use async_trait::async_trait;
-pub use plugins::{Metadata, Secrets};
-pub use shuttle_codegen::main;
+pub use plugins::{FromSecrets, Metadata, RefreshSecrets, Secrets};
+pub use shuttle_codegen::{main, FromSecrets};
 pub use shuttle_service::{
     CustomError, DbInput, DeploymentMetadata, Environment, Error, IntoResource, ResourceFactory,
     ResourceInputBuilder, SecretStore, Service,
 };
 pub use tokio;
+pub use workers::{ServiceExt, Workers};
 
 const VERSION_STRING: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
 
@@ -37,6 +40,22 @@ pub mod __internals {
     pub use serde_json;
     pub use strfmt::strfmt;
 
+    /// Get a secret parsed into `T`, or `None` if it is missing. Used by `#[derive(FromSecrets)]`.
+    pub fn parse_secret<T>(secrets: &SecretStore, key: &str) -> Result<Option<T>, String>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        secrets
+            .get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| format!("secret `{key}` is not valid: {e}"))
+            })
+            .transpose()
+    }
+
     use super::*;
     use std::future::Future;
 
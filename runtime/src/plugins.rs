@@ -1,5 +1,10 @@
+use std::collections::BTreeMap;
+
 use crate::async_trait;
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use shuttle_api_client::ShuttleApiClient;
+use shuttle_common::secrets::Secret;
 use shuttle_service::{
     resource::{ProvisionResourceRequest, ResourceType},
     DeploymentMetadata, Error, IntoResource, ResourceFactory, ResourceInputBuilder, SecretStore,
@@ -41,6 +46,9 @@ impl ResourceInputBuilder for Metadata {
 /// Next, add `#[shuttle_runtime::Secrets] secrets: SecretStore` as a parameter to your `shuttle_service::main` function.
 /// `SecretStore::get` can now be called to retrieve your API keys and other secrets at runtime.
 ///
+/// Instead of a `SecretStore`, the parameter can also be a struct that derives [`FromSecrets`].
+/// A `SecretStore` can get updated secrets later with [`RefreshSecrets::refresh`].
+///
 /// ### Example
 ///
 /// ```rust,ignore
@@ -77,9 +85,59 @@ impl ResourceInputBuilder for Secrets {
     }
 }
 
+/// Build a value from the secrets of a deployment, see [`Secrets`].
+///
+/// Usually derived with `#[derive(shuttle_runtime::FromSecrets)]` on a config struct.
+pub trait FromSecrets: Sized {
+    fn from_secrets(secrets: &SecretStore) -> Result<Self, Error>;
+}
+
+impl FromSecrets for SecretStore {
+    fn from_secrets(secrets: &SecretStore) -> Result<Self, Error> {
+        Ok(secrets.clone())
+    }
+}
+
 #[async_trait]
-impl IntoResource<SecretStore> for SecretsOutputWrapper {
-    async fn into_resource(self) -> Result<SecretStore, Error> {
-        Ok(self.0)
+impl<T: FromSecrets + Send> IntoResource<T> for SecretsOutputWrapper {
+    async fn into_resource(self) -> Result<T, Error> {
+        T::from_secrets(&self.0)
+    }
+}
+
+/// Adds [`RefreshSecrets::refresh`] to [`SecretStore`]
+#[async_trait]
+pub trait RefreshSecrets {
+    /// Get the latest secrets of the project, so that updated secrets can be used without a redeploy.
+    ///
+    /// ```rust,ignore
+    /// use shuttle_runtime::RefreshSecrets;
+    ///
+    /// secrets.refresh().await?;
+    /// let key = secrets.get("MY_API_KEY");
+    /// ```
+    async fn refresh(&mut self) -> Result<(), Error>;
+}
+
+#[async_trait]
+impl RefreshSecrets for SecretStore {
+    async fn refresh(&mut self) -> Result<(), Error> {
+        // Same API and project that the secrets were loaded from on startup
+        let api_url = std::env::var("SHUTTLE_API").context("api url env var")?;
+        let api_key = std::env::var("SHUTTLE_API_KEY").ok();
+        let project_id = std::env::var("SHUTTLE_PROJECT_ID").context("project id env var")?;
+
+        let client = ShuttleApiClient::new(api_url, api_key, None, None);
+        let secrets: BTreeMap<String, String> =
+            serde_json::from_value(client.get_secrets(&project_id).await?.output)
+                .context("failed to deserialize secrets")?;
+        *self = SecretStore::new(
+            secrets
+                .into_iter()
+                .map(|(k, v)| (k, Secret::new(v)))
+                .collect(),
+        );
+
+        Ok(())
     }
 }
@@ -1,25 +1,43 @@
 use std::{
+    backtrace::{Backtrace, BacktraceStatus},
     collections::BTreeMap,
     iter::FromIterator,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    panic::PanicHookInfo,
     process::exit,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use anyhow::Context;
 use http_body_util::Empty;
-use hyper::{body::Bytes, server::conn::http1, service::service_fn, Response};
+use hyper::{
+    body::Bytes, client::conn::http1 as client_http1, header, server::conn::http1,
+    service::service_fn, Request, Response, StatusCode,
+};
 use hyper_util::rt::TokioIo;
 use shuttle_api_client::ShuttleApiClient;
 use shuttle_common::{
     models::resource::{ResourceInput, ResourceState, ResourceType},
     secrets::Secret,
 };
-use shuttle_service::{Environment, ResourceFactory, Service};
-use tokio::net::TcpListener;
+use shuttle_service::{BuildMeta, Environment, ResourceFactory, Service};
+use tokio::net::{TcpListener, TcpStream};
 use tracing::{debug, info, trace};
 
 use crate::__internals::{Loader, Runner};
 
+/// How often to log that a resource is still being provisioned
+const PROVISIONING_REPORT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Backtrace of a panic in `bind`, kept to report why the service crashed
+static PANIC_BACKTRACE: Mutex<Option<String>> = Mutex::new(None);
+
+tokio::task_local! {
+    /// Set while polling the `bind` task, so that panics in tasks spawned by the service are left alone
+    static IN_BIND: ();
+}
+
 struct RuntimeEnvVars {
     /// Are we running in a Shuttle deployment?
     shuttle: bool,
@@ -32,10 +50,16 @@ struct RuntimeEnvVars {
     port: u16,
     /// Optional port to open health check on
     healthz_port: Option<u16>,
+    /// Optional path on the service to forward health checks to
+    health_check_path: Option<String>,
     /// Where to reach the required Shuttle API endpoints (mainly for provisioning)
     api_url: String,
     /// Key for the API calls (if relevant)
     api_key: Option<String>,
+    /// ID of this deployment (if relevant)
+    deployment_id: Option<String>,
+    /// Git info of the deployed source code (if relevant)
+    build_meta: Option<BuildMeta>,
 }
 
 impl RuntimeEnvVars {
@@ -62,13 +86,35 @@ impl RuntimeEnvVars {
             healthz_port: std::env::var("SHUTTLE_HEALTHZ_PORT")
                 .map(|s| s.parse().expect("invalid healthz port"))
                 .ok(),
+            health_check_path: std::env::var("SHUTTLE_HEALTH_CHECK_PATH").ok(),
             api_url: std::env::var("SHUTTLE_API").expect("api url env var"),
             api_key: std::env::var("SHUTTLE_API_KEY").ok(),
+            deployment_id: std::env::var("SHUTTLE_DEPLOYMENT_ID").ok(),
+            build_meta: std::env::var("SHUTTLE_GIT_COMMIT_ID")
+                .ok()
+                .map(|commit_id| BuildMeta {
+                    git_commit_id: Some(commit_id),
+                    git_commit_msg: std::env::var("SHUTTLE_GIT_COMMIT_MSG").ok(),
+                    git_branch: std::env::var("SHUTTLE_GIT_BRANCH").ok(),
+                    git_dirty: std::env::var("SHUTTLE_GIT_DIRTY").ok().map(|d| d == "true"),
+                }),
         }
     }
 }
 
-pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + Send + 'static) {
+#[cfg_attr(not(feature = "setup-otel-exporter"), allow(unused_variables))]
+pub async fn start(
+    loader: impl Loader + Send + 'static,
+    runner: impl Runner + Send + 'static,
+    crate_name: &'static str,
+    package_version: &'static str,
+) {
+    // The exporters are configured from secrets, so logs are only printed until those are fetched.
+    // This is the default of the current thread, which runs this future until the global one is set.
+    #[cfg(feature = "setup-otel-exporter")]
+    let startup_guard =
+        tracing::subscriber::set_default(crate::telemetry::startup_subscriber(crate_name));
+
     debug!("Parsing environment variables");
     let RuntimeEnvVars {
         shuttle,
@@ -78,8 +124,11 @@ pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + S
         ip,
         port,
         healthz_port,
+        health_check_path,
         api_url,
         api_key,
+        deployment_id,
+        build_meta,
     } = RuntimeEnvVars::parse();
 
     let service_addr = SocketAddr::new(ip, port);
@@ -102,18 +151,28 @@ pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + S
                     exit(202);
                 };
                 let io = TokioIo::new(stream);
+                let health_check_path = health_check_path.clone();
 
                 tokio::task::spawn(async move {
                     if let Err(err) = http1::Builder::new()
                         .serve_connection(
                             io,
-                            service_fn(|_req| async move {
-                                trace!("Received health check");
-                                // TODO: A hook into the `Service` trait can be added here
-                                trace!("Responding to health check");
-                                Result::<Response<Empty<Bytes>>, hyper::Error>::Ok(Response::new(
-                                    Empty::new(),
-                                ))
+                            service_fn(|_req| {
+                                let health_check_path = health_check_path.clone();
+                                async move {
+                                    trace!("Received health check");
+                                    // TODO: A hook into the `Service` trait can be added here
+                                    let healthy = match health_check_path {
+                                        Some(path) => probe_service(service_addr, &path).await,
+                                        None => true,
+                                    };
+                                    trace!(healthy, "Responding to health check");
+                                    let mut res = Response::new(Empty::<Bytes>::new());
+                                    if !healthy {
+                                        *res.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+                                    }
+                                    Result::<Response<Empty<Bytes>>, hyper::Error>::Ok(res)
+                                }
                             }),
                         )
                         .await
@@ -144,11 +203,23 @@ pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + S
         }
     };
 
+    #[cfg(feature = "setup-otel-exporter")]
+    let _guard = {
+        drop(startup_guard);
+        let config = crate::telemetry::ExporterConfig::from_env_and_secrets(&secrets);
+        let guard = crate::telemetry::init_tracing_subscriber(crate_name, package_version, config);
+        tracing::warn!(
+            "Default tracing subscriber initialized (https://docs.shuttle.dev/docs/logs)"
+        );
+        guard
+    };
+
     // Sort secrets by key
     let secrets = BTreeMap::from_iter(secrets.into_iter().map(|(k, v)| (k, Secret::new(v))));
 
     // TODO: rework `ResourceFactory`
-    let factory = ResourceFactory::new(project_name, secrets.clone(), env);
+    let factory = ResourceFactory::new(project_name, secrets.clone(), env)
+        .with_deployment(deployment_id, build_meta);
     let mut resources = match loader.load(factory).await {
         Ok(r) => r,
         Err(e) => {
@@ -188,6 +259,10 @@ pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + S
         }
 
         info!("Provisioning {:?}", shuttle_resource.r#type);
+        // Some resources take minutes to create, so keep the deploy logs updated while waiting
+        let started = Instant::now();
+        let mut last_state = None;
+        let mut last_report = started;
         loop {
             trace!("Checking state of {:?}", shuttle_resource.r#type);
             match client
@@ -198,10 +273,29 @@ pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + S
                     trace!("Got response {:?}", res);
                     match res.state {
                         ResourceState::Provisioning | ResourceState::Authorizing => {
+                            if last_state.as_ref() != Some(&res.state)
+                                || last_report.elapsed() >= PROVISIONING_REPORT_INTERVAL
+                            {
+                                info!(
+                                    "Waiting for {} to be ready (state: {}, {}s elapsed)",
+                                    shuttle_resource.r#type,
+                                    res.state,
+                                    started.elapsed().as_secs()
+                                );
+                                last_report = Instant::now();
+                            }
+                            last_state = Some(res.state.clone());
                             tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
                         }
                         ResourceState::Ready => {
-                            *bytes = serde_json::to_vec(&res.output).expect("to serialize struct");
+                            if last_state.is_some() {
+                                info!(
+                                    "{} is ready after {}s",
+                                    shuttle_resource.r#type,
+                                    started.elapsed().as_secs()
+                                );
+                            }
+                            *bytes = with_request_state(bytes, res.output);
                             break;
                         }
                         bad_state => {
@@ -248,8 +342,123 @@ pub async fn start(loader: impl Loader + Send + 'static, runner: impl Runner + S
     //
     info!("Starting service");
 
-    if let Err(e) = service.bind(service_addr).await {
-        eprintln!("ERROR: Service encountered an error in `bind`: {e}");
-        exit(1);
+    let default_hook = Arc::<dyn Fn(&PanicHookInfo) + Send + Sync>::from(std::panic::take_hook());
+    let hook = default_hook.clone();
+    std::panic::set_hook(Box::new(move |info| {
+        hook(info);
+        // The default hook already printed the backtrace if RUST_BACKTRACE is set
+        if IN_BIND.try_with(|_| ()).is_ok()
+            && Backtrace::capture().status() == BacktraceStatus::Disabled
+        {
+            *PANIC_BACKTRACE.lock().unwrap() = Some(Backtrace::force_capture().to_string());
+        }
+    }));
+
+    // Run in its own task to catch panics
+    let res = tokio::spawn(IN_BIND.scope((), service.bind(service_addr))).await;
+    std::panic::set_hook(Box::new(move |info| default_hook(info)));
+
+    match res {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            eprintln!("ERROR: Service encountered an error in `bind`: {e}");
+            exit(1);
+        }
+        Err(e) => {
+            let payload = match e.try_into_panic() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    eprintln!("ERROR: Service encountered an error in `bind`: {e}");
+                    exit(1);
+                }
+            };
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("(no message)");
+            let backtrace = PANIC_BACKTRACE.lock().unwrap().take().unwrap_or_default();
+            eprintln!("ERROR: Service panicked in `bind`: {message}\n{backtrace}");
+            exit(2);
+        }
+    }
+}
+
+/// Send a request to the health check path of the service and see if it responds with a success status
+async fn probe_service(service_addr: SocketAddr, path: &str) -> bool {
+    // The service might listen on all interfaces, so connect to it through localhost in that case
+    let addr = if service_addr.ip().is_unspecified() {
+        SocketAddr::new(Ipv4Addr::LOCALHOST.into(), service_addr.port())
+    } else {
+        service_addr
+    };
+
+    let probe = async {
+        let stream = TcpStream::connect(addr).await.ok()?;
+        let (mut sender, conn) = client_http1::handshake(TokioIo::new(stream)).await.ok()?;
+        tokio::spawn(conn);
+        let req = Request::get(path)
+            .header(header::HOST, addr.to_string())
+            .body(Empty::<Bytes>::new())
+            .ok()?;
+        let res = sender.send_request(req).await.ok()?;
+
+        Some(res.status().is_success())
+    };
+
+    match tokio::time::timeout(Duration::from_secs(5), probe).await {
+        Ok(Some(healthy)) => healthy,
+        _ => false,
+    }
+}
+
+/// The bytes to hand to a resource's runner after it was provisioned.
+///
+/// Fields a resource put next to its provision request are its own state (like a migrations path),
+/// so they are handed back together with the provisioned output under the `output` key.
+fn with_request_state(input: &[u8], output: serde_json::Value) -> Vec<u8> {
+    let mut state = serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(input)
+        .unwrap_or_default();
+    state.remove("type");
+    state.remove("config");
+
+    if state.is_empty() {
+        serde_json::to_vec(&output).expect("to serialize struct")
+    } else {
+        state.insert("output".to_owned(), output);
+        serde_json::to_vec(&state).expect("to serialize struct")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::with_request_state;
+
+    #[test]
+    fn request_state_is_kept_with_output() {
+        let plain =
+            serde_json::to_vec(&json!({"type": "database::shared::postgres", "config": {}}))
+                .unwrap();
+        assert_eq!(
+            with_request_state(&plain, json!("postgres://localhost")),
+            serde_json::to_vec(&json!("postgres://localhost")).unwrap()
+        );
+
+        let with_state = serde_json::to_vec(&json!({
+            "type": "database::shared::postgres",
+            "config": {},
+            "migrations": "./migrations",
+        }))
+        .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&with_request_state(
+                &with_state,
+                json!("postgres://localhost")
+            ))
+            .unwrap(),
+            json!({"output": "postgres://localhost", "migrations": "./migrations"})
+        );
     }
 }
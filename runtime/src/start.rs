@@ -48,8 +48,11 @@ pub async fn start(
     #[cfg(all(feature = "setup-tracing", not(feature = "setup-otel-exporter")))]
     {
         use tracing_subscriber::{fmt, prelude::*, registry, EnvFilter};
+        // JSON lines keep the fields of log events apart from the message, so they can be shown as structured data
+        let json = std::env::var("SHUTTLE_LOG_FORMAT").is_ok_and(|f| f == "json");
         registry()
-            .with(fmt::layer().without_time())
+            .with((!json).then(|| fmt::layer().without_time()))
+            .with(json.then(|| fmt::layer().without_time().json().flatten_event(true)))
             .with(
                 // let user override RUST_LOG in local run if they want to
                 EnvFilter::try_from_default_env().unwrap_or_else(|_| {
@@ -60,11 +63,9 @@ pub async fn start(
             .init();
     }
 
-    #[cfg(feature = "setup-otel-exporter")]
-    let _guard = crate::telemetry::init_tracing_subscriber(crate_name, package_version);
-
-    #[cfg(any(feature = "setup-tracing", feature = "setup-otel-exporter"))]
+    #[cfg(all(feature = "setup-tracing", not(feature = "setup-otel-exporter")))]
     tracing::warn!("Default tracing subscriber initialized (https://docs.shuttle.dev/docs/logs)");
 
-    rt::start(loader, runner).await
+    // with `setup-otel-exporter`, the runtime sets up tracing and its exporters from secrets
+    rt::start(loader, runner, crate_name, package_version).await
 }
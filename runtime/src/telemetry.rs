@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     marker::PhantomData,
     sync::Arc,
     time::{Duration, SystemTime},
@@ -11,7 +11,9 @@ use opentelemetry::{
     trace::{SpanId, TraceId, TracerProvider as _},
     KeyValue,
 };
-use opentelemetry_otlp::{WithExportConfig, OTEL_EXPORTER_OTLP_ENDPOINT};
+use opentelemetry_otlp::{
+    WithExportConfig, WithHttpConfig, OTEL_EXPORTER_OTLP_ENDPOINT, OTEL_EXPORTER_OTLP_HEADERS,
+};
 use opentelemetry_sdk::{
     logs::{LogRecord, Logger, LoggerProvider},
     metrics::{MeterProviderBuilder, PeriodicReader, SdkMeterProvider, Temporality},
@@ -395,12 +397,48 @@ pub fn resource(crate_name: &'static str, package_version: &'static str) -> Reso
         )))
 }
 
-pub fn init_log_subscriber(endpoint: &Option<String>, resource: Resource) -> LoggerProvider {
+/// Where to export telemetry to, and which headers (such as auth tokens) to send along
+#[derive(Default)]
+pub struct ExporterConfig {
+    endpoint: Option<String>,
+    headers: HashMap<String, String>,
+}
+
+impl ExporterConfig {
+    /// Reads the standard `OTEL_EXPORTER_OTLP_*` variables from the environment, falling back to
+    /// secrets with the same names so that deployed services can ship to their own backend.
+    pub fn from_env_and_secrets(secrets: &BTreeMap<String, String>) -> Self {
+        // The env var is useful for setting a specific host when running locally
+        let endpoint = std::env::var(OTEL_EXPORTER_OTLP_ENDPOINT)
+            .ok()
+            .or_else(|| secrets.get(OTEL_EXPORTER_OTLP_ENDPOINT).cloned());
+        // Headers from the env var are added by the exporters themselves
+        let headers = secrets
+            .get(OTEL_EXPORTER_OTLP_HEADERS)
+            .map(|h| parse_headers(h))
+            .unwrap_or_default();
+
+        Self { endpoint, headers }
+    }
+}
+
+/// Parse headers in the `key1=value1,key2=value2` format used by `OTEL_EXPORTER_OTLP_HEADERS`
+fn parse_headers(input: &str) -> HashMap<String, String> {
+    input
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.trim().to_owned(), v.trim().to_owned()))
+        .filter(|(k, _)| !k.is_empty())
+        .collect()
+}
+
+pub fn init_log_subscriber(config: &ExporterConfig, resource: Resource) -> LoggerProvider {
     let mut builder = LoggerProvider::builder().with_resource(resource);
 
-    if let Some(endpoint) = endpoint {
+    if let Some(endpoint) = &config.endpoint {
         let exporter = opentelemetry_otlp::LogExporter::builder()
             .with_http()
+            .with_headers(config.headers.clone())
             .with_endpoint(format!("{endpoint}/v1/logs"))
             .build()
             .unwrap();
@@ -412,13 +450,14 @@ pub fn init_log_subscriber(endpoint: &Option<String>, resource: Resource) -> Log
 }
 
 // Construct MeterProvider for MetricsLayer
-pub fn init_meter_provider(endpoint: &Option<String>, resource: Resource) -> SdkMeterProvider {
+pub fn init_meter_provider(config: &ExporterConfig, resource: Resource) -> SdkMeterProvider {
     let mut builder = MeterProviderBuilder::default().with_resource(resource);
 
-    if let Some(endpoint) = endpoint {
+    if let Some(endpoint) = &config.endpoint {
         let exporter = opentelemetry_otlp::MetricExporter::builder()
             .with_temporality(Temporality::default())
             .with_http()
+            .with_headers(config.headers.clone())
             .with_endpoint(format!("{endpoint}/v1/metrics"))
             .build()
             .unwrap();
@@ -438,7 +477,7 @@ pub fn init_meter_provider(endpoint: &Option<String>, resource: Resource) -> Sdk
 }
 
 // Construct TracerProvider for OpenTelemetryLayer
-pub fn init_tracer_provider(endpoint: &Option<String>, resource: Resource) -> TracerProvider {
+pub fn init_tracer_provider(config: &ExporterConfig, resource: Resource) -> TracerProvider {
     // TODO(the-wondersmith): make trace sample rate & strategy configurable
     // let sampler = opentelemetry_sdk::trace::Sampler::ParentBased(Box::new(
     //     opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(1.0),
@@ -448,9 +487,10 @@ pub fn init_tracer_provider(endpoint: &Option<String>, resource: Resource) -> Tr
         // .with_sampler(sampler)
         .with_resource(resource);
 
-    if let Some(endpoint) = endpoint {
+    if let Some(endpoint) = &config.endpoint {
         let exporter = opentelemetry_otlp::SpanExporter::builder()
             .with_http()
+            .with_headers(config.headers.clone())
             .with_endpoint(format!("{endpoint}/v1/traces"))
             .build()
             .unwrap();
@@ -465,41 +505,54 @@ pub fn init_tracer_provider(endpoint: &Option<String>, resource: Resource) -> Tr
     provider
 }
 
+fn level_filter(crate_name: &'static str) -> EnvFilter {
+    EnvFilter::from(
+        std::env::var("RUST_LOG").unwrap_or_else(|_| format!("info,{}=debug", crate_name)),
+    )
+}
+
+fn fmt_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_subscriber::fmt::layer()
+        .compact()
+        .with_level(true)
+        .with_target(true)
+}
+
+/// Subscriber that only prints logs, for the startup phase before the exporter config is known
+pub fn startup_subscriber(crate_name: &'static str) -> impl Subscriber + Send + Sync {
+    tracing_subscriber::registry()
+        .with(level_filter(crate_name))
+        .with(fmt_layer())
+}
+
 // Initialize tracing-subscriber and return ExporterGuard for opentelemetry-related termination processing
 pub fn init_tracing_subscriber(
     crate_name: &'static str,
     package_version: &'static str,
+    config: ExporterConfig,
 ) -> ProviderGuard {
     global::set_text_map_propagator(TraceContextPropagator::new());
 
     let resource = resource(crate_name, package_version);
 
-    // The OTLP_HOST env var is useful for setting a specific host when running locally
-    let endpoint = std::env::var(OTEL_EXPORTER_OTLP_ENDPOINT).ok();
-
-    let tracer = init_tracer_provider(&endpoint, resource.clone());
-    let meter = init_meter_provider(&endpoint, resource.clone());
-    let logger = init_log_subscriber(&endpoint, resource);
+    let tracer = init_tracer_provider(&config, resource.clone());
+    let meter = init_meter_provider(&config, resource.clone());
+    let logger = init_log_subscriber(&config, resource);
 
-    let level_filter =
-        std::env::var("RUST_LOG").unwrap_or_else(|_| format!("info,{}=debug", crate_name));
-
-    let layers = EnvFilter::from(&level_filter)
+    let layers = level_filter(crate_name)
         .and_then(MetricsLayer::new(meter.clone()))
         .and_then(OpenTelemetryLayer::new(tracer.tracer("shuttle-telemetry")))
-        .and_then(
-            tracing_subscriber::fmt::layer()
-                .compact()
-                .with_level(true)
-                .with_target(true),
-        )
+        .and_then(fmt_layer())
         .and_then(LogCourier::new(logger.logger("shuttle-telemetry")));
 
     tracing_subscriber::registry().with(layers).init();
 
-    if endpoint.is_none() {
+    if config.endpoint.is_none() {
         tracing::warn!(
-            "No value set for `OTEL_EXPORTER_OTLP_ENDPOINT` env var, \
+            "No value set for `OTEL_EXPORTER_OTLP_ENDPOINT` env var or secret, \
             declining to attach OTLP exporter to default tracing subscriber"
         );
     }
@@ -510,3 +563,20 @@ pub fn init_tracing_subscriber(
         meter,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_headers() {
+        let headers = parse_headers("api-key=abc123, x-team = backend,invalid,=empty");
+        assert_eq!(
+            headers,
+            HashMap::from([
+                ("api-key".to_owned(), "abc123".to_owned()),
+                ("x-team".to_owned(), "backend".to_owned()),
+            ])
+        );
+    }
+}
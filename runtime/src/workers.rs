@@ -0,0 +1,150 @@
+use std::{future::Future, net::SocketAddr, pin::Pin, sync::Arc, time::Duration};
+
+use tokio::{task::JoinSet, time::Instant};
+
+use crate::{async_trait, Error, Service};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+type WorkerFuture = Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>;
+type WorkerFn = Arc<dyn Fn() -> WorkerFuture + Send + Sync>;
+
+struct Worker {
+    name: String,
+    run: WorkerFn,
+}
+
+/// A [`Service`] that runs background workers next to another service.
+///
+/// Workers that panic or return an error are restarted with an exponential backoff.
+/// Workers that return `Ok(())` are done and not restarted.
+/// All workers are stopped when the inner service stops.
+///
+/// ```rust,ignore
+/// use shuttle_runtime::ServiceExt;
+///
+/// #[shuttle_runtime::main]
+/// async fn main() -> Result<shuttle_runtime::Workers<shuttle_axum::AxumService>, shuttle_runtime::Error> {
+///     let router = Router::new().route("/", get(hello_world));
+///
+///     Ok(shuttle_axum::AxumService::from(router).with_worker("mailer", || async {
+///         send_queued_emails().await?;
+///         Ok(())
+///     }))
+/// }
+/// ```
+pub struct Workers<S> {
+    service: S,
+    workers: Vec<Worker>,
+}
+
+impl<S: Service> Workers<S> {
+    /// Add another worker. The closure is called again to restart the worker.
+    pub fn with_worker<F, Fut>(mut self, name: &str, worker: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        self.workers.push(Worker {
+            name: name.to_owned(),
+            run: Arc::new(move || Box::pin(worker())),
+        });
+        self
+    }
+}
+
+/// Adds [`ServiceExt::with_worker`] to all services
+pub trait ServiceExt: Service + Sized {
+    /// Run a background worker next to this service. See [`Workers`].
+    fn with_worker<F, Fut>(self, name: &str, worker: F) -> Workers<Self>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
+    {
+        Workers {
+            service: self,
+            workers: Vec::new(),
+        }
+        .with_worker(name, worker)
+    }
+}
+
+impl<S: Service> ServiceExt for S {}
+
+#[async_trait]
+impl<S: Service> Service for Workers<S> {
+    async fn bind(self, addr: SocketAddr) -> Result<(), Error> {
+        // Dropping the set at the end of this function stops the workers
+        let mut set = JoinSet::new();
+        for worker in self.workers {
+            set.spawn(supervise(worker));
+        }
+
+        self.service.bind(addr).await
+    }
+}
+
+/// Run a worker until it returns `Ok(())`, restarting it when it fails
+async fn supervise(worker: Worker) {
+    let mut backoff = INITIAL_BACKOFF;
+    loop {
+        let started = Instant::now();
+        // Run in its own task to catch panics. The set aborts the task if the supervisor is stopped.
+        let mut run = JoinSet::new();
+        run.spawn((worker.run)());
+        let error = match run.join_next().await.expect("one task in set") {
+            Ok(Ok(())) => return,
+            Ok(Err(e)) => format!("failed: {e:?}"),
+            Err(e) => format!("panicked: {e}"),
+        };
+
+        // A worker that ran for a while before failing starts over with a short backoff
+        if started.elapsed() > MAX_BACKOFF {
+            backoff = INITIAL_BACKOFF;
+        }
+        eprintln!(
+            "ERROR: Worker '{}' {error}. Restarting in {}s",
+            worker.name,
+            backoff.as_secs()
+        );
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    struct SleepService(Duration);
+
+    #[async_trait]
+    impl Service for SleepService {
+        async fn bind(self, _addr: SocketAddr) -> Result<(), Error> {
+            tokio::time::sleep(self.0).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn restarts_failed_worker() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        let service = SleepService(Duration::from_millis(1500)).with_worker("test", move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("first run fails");
+                }
+                Ok(())
+            }
+        });
+
+        service.bind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+}
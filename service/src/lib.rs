@@ -7,11 +7,12 @@ use serde::{de::DeserializeOwned, Serialize};
 use shuttle_common::constants::STORAGE_DIRNAME;
 pub use shuttle_common::{
     models::{
-        deployment::{DeploymentMetadata, Environment},
+        deployment::{BuildMeta, DeploymentMetadata, Environment},
         resource,
     },
     secrets::{Secret, SecretStore},
-    ContainerRequest, ContainerResponse, DatabaseInfo, DatabaseResource, DbInput,
+    ContainerRequest, ContainerResponse, CustomResourceInput, DatabaseInfo, DatabaseResource,
+    DbInput,
 };
 
 pub use crate::error::{CustomError, Error};
@@ -28,6 +29,8 @@ pub mod error;
 /// You can also make your own plugin, for example to generalise the connection logic to a third-party service.
 /// One example of this is `shuttle-qdrant`.
 ///
+/// Plugins that need Shuttle to keep some data for them can use [`ResourceFactory::custom_resource`] as their input.
+///
 /// Please refer to `shuttle-examples/custom-resource` for examples of how to create a custom resource. For more advanced provisioning
 /// of custom resources, please [get in touch](https://discord.gg/shuttle) and detail your use case. We'll be interested to see what you
 /// want to provision and how to do it on your behalf on the fly.
@@ -38,6 +41,8 @@ pub trait ResourceInputBuilder: Default {
     /// If the input is a [`shuttle_common::resource::ProvisionResourceRequest`],
     /// then the resource will be provisioned and the associated output type will
     /// be put in [`ResourceInputBuilder::Output`].
+    /// Any extra fields serialized next to the request are kept, and the output is then
+    /// put under an `output` key next to them.
     type Input: Serialize + DeserializeOwned;
 
     /// The output from provisioning this resource.
@@ -57,6 +62,8 @@ pub struct ResourceFactory {
     project_name: String,
     secrets: BTreeMap<String, Secret<String>>,
     env: Environment,
+    deployment_id: Option<String>,
+    build_meta: Option<BuildMeta>,
 }
 
 impl ResourceFactory {
@@ -69,18 +76,57 @@ impl ResourceFactory {
             project_name,
             secrets,
             env,
+            deployment_id: None,
+            build_meta: None,
         }
     }
 
+    /// Set info about the running deployment, to be shown in [`DeploymentMetadata`]
+    pub fn with_deployment(
+        mut self,
+        deployment_id: Option<String>,
+        build_meta: Option<BuildMeta>,
+    ) -> Self {
+        self.deployment_id = deployment_id;
+        self.build_meta = build_meta;
+        self
+    }
+
     pub fn get_secrets(&self) -> BTreeMap<String, Secret<String>> {
         self.secrets.clone()
     }
 
+    /// Create the input for a custom resource of a third-party plugin.
+    ///
+    /// The resource is provisioned like other Shuttle resources, and its output is deserialized into
+    /// [`ResourceInputBuilder::Output`]. When running locally, the output is the `config` that was passed in.
+    pub fn custom_resource(
+        &self,
+        type_id: &str,
+        config: impl Serialize,
+    ) -> Result<resource::ProvisionResourceRequest, crate::Error> {
+        let config = serde_json::to_value(config)
+            .and_then(|config| {
+                serde_json::to_value(CustomResourceInput {
+                    type_id: type_id.to_owned(),
+                    config,
+                })
+            })
+            .map_err(|e| crate::Error::Custom(e.into()))?;
+
+        Ok(resource::ProvisionResourceRequest {
+            r#type: resource::ResourceType::Custom(type_id.to_owned()),
+            config,
+        })
+    }
+
     pub fn get_metadata(&self) -> DeploymentMetadata {
         DeploymentMetadata {
             env: self.env,
             project_name: self.project_name.to_string(),
             storage_path: PathBuf::from(STORAGE_DIRNAME),
+            deployment_id: self.deployment_id.clone(),
+            build_meta: self.build_meta.clone(),
         }
     }
 }
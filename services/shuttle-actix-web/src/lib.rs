@@ -1,5 +1,5 @@
 #![doc = include_str!("../README.md")]
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 pub use actix_web;
 
@@ -8,20 +8,71 @@ pub use actix_web;
 #[derive(Clone)]
 pub struct ActixWebService<F>(pub F);
 
+impl<F> ActixWebService<F> {
+    /// Set the number of workers. Defaults to one per cpu, but no more than 4.
+    pub fn with_workers(self, workers: usize) -> ActixWebServiceWithOptions<F> {
+        ActixWebServiceWithOptions::from(self).with_workers(workers)
+    }
+
+    /// Set how long open connections get to finish when the service is stopped. Defaults to 30 seconds.
+    pub fn with_shutdown_timeout(self, timeout: Duration) -> ActixWebServiceWithOptions<F> {
+        ActixWebServiceWithOptions::from(self).with_shutdown_timeout(timeout)
+    }
+}
+
 #[shuttle_runtime::async_trait]
 impl<F> shuttle_runtime::Service for ActixWebService<F>
+where
+    F: FnOnce(&mut actix_web::web::ServiceConfig) + Send + Clone + 'static,
+{
+    async fn bind(mut self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
+        shuttle_runtime::Service::bind(ActixWebServiceWithOptions::from(self), addr).await
+    }
+}
+
+/// An [ActixWebService] with server options, created with [ActixWebService::with_workers]
+/// or [ActixWebService::with_shutdown_timeout].
+#[derive(Clone)]
+pub struct ActixWebServiceWithOptions<F> {
+    service_config: F,
+    workers: Option<usize>,
+    shutdown_timeout: Option<Duration>,
+}
+
+impl<F> ActixWebServiceWithOptions<F> {
+    /// Set the number of workers. Defaults to one per cpu, but no more than 4.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = Some(workers);
+        self
+    }
+
+    /// Set how long open connections get to finish when the service is stopped. Defaults to 30 seconds.
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = Some(timeout);
+        self
+    }
+}
+
+#[shuttle_runtime::async_trait]
+impl<F> shuttle_runtime::Service for ActixWebServiceWithOptions<F>
 where
     F: FnOnce(&mut actix_web::web::ServiceConfig) + Send + Clone + 'static,
 {
     async fn bind(mut self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
         // Start a worker for each cpu, but no more than 4.
-        let worker_count = num_cpus::get().min(4);
+        let worker_count = self.workers.unwrap_or_else(|| num_cpus::get().min(4));
+
+        // Same default as Actix Web
+        let shutdown_timeout = self.shutdown_timeout.unwrap_or(Duration::from_secs(30));
 
-        let server =
-            actix_web::HttpServer::new(move || actix_web::App::new().configure(self.0.clone()))
-                .workers(worker_count)
-                .bind(addr)?
-                .run();
+        let service_config = self.service_config;
+        let server = actix_web::HttpServer::new(move || {
+            actix_web::App::new().configure(service_config.clone())
+        })
+        .workers(worker_count)
+        .shutdown_timeout(shutdown_timeout.as_secs())
+        .bind(addr)?
+        .run();
 
         server.await.map_err(shuttle_runtime::CustomError::new)?;
 
@@ -29,6 +80,16 @@ where
     }
 }
 
+impl<F> From<ActixWebService<F>> for ActixWebServiceWithOptions<F> {
+    fn from(service: ActixWebService<F>) -> Self {
+        Self {
+            service_config: service.0,
+            workers: None,
+            shutdown_timeout: None,
+        }
+    }
+}
+
 impl<F> From<F> for ActixWebService<F>
 where
     F: FnOnce(&mut actix_web::web::ServiceConfig) + Send + Clone + 'static,
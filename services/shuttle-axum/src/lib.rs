@@ -1,6 +1,6 @@
 #![doc = include_str!("../README.md")]
 use shuttle_runtime::{CustomError, Error};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 #[cfg(feature = "axum")]
 pub use axum;
@@ -15,6 +15,17 @@ use axum_0_7::Router;
 /// A wrapper type for [axum::Router] so we can implement [shuttle_runtime::Service] for it.
 pub struct AxumService(pub Router);
 
+impl AxumService {
+    /// Stop accepting connections when the service is stopped, and give open connections
+    /// this long to finish. The service is stopped right away by default.
+    pub fn with_shutdown_timeout(self, timeout: Duration) -> AxumServiceWithOptions {
+        AxumServiceWithOptions {
+            router: self.0,
+            shutdown_timeout: timeout,
+        }
+    }
+}
+
 #[shuttle_runtime::async_trait]
 impl shuttle_runtime::Service for AxumService {
     /// Takes the router that is returned by the user in their [shuttle_runtime::main] function
@@ -43,6 +54,65 @@ impl shuttle_runtime::Service for AxumService {
     }
 }
 
+/// An [AxumService] that shuts down gracefully, created with [AxumService::with_shutdown_timeout].
+pub struct AxumServiceWithOptions {
+    router: Router,
+    shutdown_timeout: Duration,
+}
+
+impl AxumServiceWithOptions {
+    /// Change how long open connections get to finish when the service is stopped.
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+}
+
+#[shuttle_runtime::async_trait]
+impl shuttle_runtime::Service for AxumServiceWithOptions {
+    async fn bind(mut self, addr: SocketAddr) -> Result<(), Error> {
+        let listener = shuttle_runtime::tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(CustomError::new)?;
+
+        let (stopping_tx, stopping_rx) = shuttle_runtime::tokio::sync::oneshot::channel();
+        let signal = async move {
+            shutdown_signal().await;
+            let _ = stopping_tx.send(());
+        };
+        #[cfg(feature = "axum")]
+        let serve = axum::serve(listener, self.router).with_graceful_shutdown(signal);
+        #[cfg(feature = "axum-0-7")]
+        let serve = axum_0_7::serve(listener, self.router).with_graceful_shutdown(signal);
+
+        shuttle_runtime::tokio::select! {
+            res = serve => res.map_err(CustomError::new)?,
+            _ = async {
+                let _ = stopping_rx.await;
+                shuttle_runtime::tokio::time::sleep(self.shutdown_timeout).await;
+            } => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Wait for Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use shuttle_runtime::tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm = signal(SignalKind::terminate()).expect("to listen for SIGTERM");
+        shuttle_runtime::tokio::select! {
+            _ = shuttle_runtime::tokio::signal::ctrl_c() => {}
+            _ = sigterm.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = shuttle_runtime::tokio::signal::ctrl_c().await;
+}
+
 impl From<Router> for AxumService {
     fn from(router: Router) -> Self {
         Self(router)
@@ -0,0 +1,97 @@
+#![doc = include_str!("../README.md")]
+use std::{future::Future, net::SocketAddr, pin::Pin, str::FromStr, sync::Arc};
+
+use chrono::Utc;
+use cron::Schedule;
+use shuttle_runtime::{tokio, CustomError, Error};
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<(), CustomError>> + Send>>;
+type JobFn = Arc<dyn Fn() -> JobFuture + Send + Sync>;
+
+struct Job {
+    schedule: Schedule,
+    run: JobFn,
+}
+
+/// A set of jobs that run on cron schedules, which implements [shuttle_runtime::Service].
+#[derive(Default)]
+pub struct CronService {
+    jobs: Vec<Job>,
+}
+
+impl CronService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a job that runs on a cron schedule, such as `"0 */5 * * * *"` for every five minutes.
+    ///
+    /// The schedule has the fields `sec min hour day_of_month month day_of_week [year]` and is
+    /// evaluated in UTC. A run of a job that fails is logged, and the job runs again on its next
+    /// scheduled time.
+    pub fn job<F, Fut>(mut self, schedule: &str, job: F) -> Result<Self, Error>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), CustomError>> + Send + 'static,
+    {
+        let schedule = Schedule::from_str(schedule).map_err(CustomError::new)?;
+        self.jobs.push(Job {
+            schedule,
+            run: Arc::new(move || Box::pin(job())),
+        });
+
+        Ok(self)
+    }
+}
+
+#[shuttle_runtime::async_trait]
+impl shuttle_runtime::Service for CronService {
+    /// Takes the jobs that are returned by the user in their [shuttle_runtime::main] function
+    /// and runs each of them on its schedule.
+    async fn bind(self, _addr: SocketAddr) -> Result<(), Error> {
+        if self.jobs.is_empty() {
+            return Err(Error::Custom(CustomError::msg("no jobs were added")));
+        }
+
+        let mut set = tokio::task::JoinSet::new();
+        for Job { schedule, run } in self.jobs {
+            let expression = schedule.to_string();
+            set.spawn(async move {
+                for next in schedule.upcoming(Utc) {
+                    let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                    tokio::time::sleep(wait).await;
+
+                    // Run each job in its own task so a slow run does not delay the next one
+                    let run = Arc::clone(&run);
+                    let expression = expression.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = run().await {
+                            eprintln!("ERROR: Scheduled job ({expression}) failed: {e:?}");
+                        }
+                    });
+                }
+            });
+        }
+        while set.join_next().await.is_some() {}
+
+        Ok(())
+    }
+}
+
+#[doc = include_str!("../README.md")]
+pub type ShuttleCron = Result<CronService, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_schedule() {
+        assert!(CronService::new()
+            .job("* * * * * *", || async { Ok(()) })
+            .is_ok());
+        assert!(CronService::new()
+            .job("every hour", || async { Ok(()) })
+            .is_err());
+    }
+}
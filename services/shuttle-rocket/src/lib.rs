@@ -1,20 +1,40 @@
 #![doc = include_str!("../README.md")]
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 pub use rocket;
 
 /// A wrapper type for [rocket::Rocket<rocket::Build>] so we can implement [shuttle_runtime::Service] for it.
 pub struct RocketService(pub rocket::Rocket<rocket::Build>);
 
+impl RocketService {
+    /// Set how long open connections get to finish when the service is stopped.
+    /// Defaults to Rocket's own shutdown grace period.
+    pub fn with_shutdown_timeout(self, timeout: Duration) -> Self {
+        let grace: u32 = timeout.as_secs().try_into().unwrap_or(u32::MAX);
+        self.with_config(("shutdown.grace", grace))
+    }
+
+    /// Merge Rocket configuration over the configuration of the instance, e.g. limits or TLS.
+    /// The address and port are always the ones given by Shuttle.
+    pub fn with_config(mut self, provider: impl rocket::figment::Provider) -> Self {
+        let figment = self.0.figment().clone().merge(provider);
+        self.0 = self.0.configure(figment);
+        self
+    }
+}
+
 #[shuttle_runtime::async_trait]
 impl shuttle_runtime::Service for RocketService {
     /// Takes the router that is returned by the user in their [shuttle_runtime::main] function
     /// and binds to an address passed in by shuttle.
     async fn bind(mut self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
-        let shutdown = rocket::config::Shutdown {
-            ctrlc: false,
-            ..rocket::config::Shutdown::default()
-        };
+        // Keep the configured shutdown settings, but let the runtime handle signals
+        let mut shutdown = self
+            .0
+            .figment()
+            .extract_inner::<rocket::config::Shutdown>(rocket::Config::SHUTDOWN)
+            .unwrap_or_default();
+        shutdown.ctrlc = false;
 
         let config = self
             .0
@@ -8,6 +8,19 @@ pub use salvo;
 /// A wrapper type for [salvo::Router] so we can implement [shuttle_runtime::Service] for it.
 pub struct SalvoService(pub salvo::Router);
 
+impl SalvoService {
+    /// Set the time-to-live of packets sent by the TCP listener.
+    pub fn with_ttl(self, ttl: u32) -> SalvoServiceWithOptions {
+        SalvoServiceWithOptions::from(self).with_ttl(ttl)
+    }
+
+    /// Serve HTTPS with this TLS configuration instead of plain HTTP.
+    #[cfg(feature = "rustls")]
+    pub fn with_rustls(self, config: salvo::conn::rustls::RustlsConfig) -> SalvoServiceWithOptions {
+        SalvoServiceWithOptions::from(self).with_rustls(config)
+    }
+}
+
 #[shuttle_runtime::async_trait]
 impl shuttle_runtime::Service for SalvoService {
     /// Takes the router that is returned by the user in their [shuttle_runtime::main] function
@@ -27,5 +40,65 @@ impl From<salvo::Router> for SalvoService {
     }
 }
 
+/// A [SalvoService] with listener options, created with [SalvoService::with_ttl]
+/// or `SalvoService::with_rustls`.
+pub struct SalvoServiceWithOptions {
+    router: salvo::Router,
+    ttl: Option<u32>,
+    #[cfg(feature = "rustls")]
+    rustls: Option<salvo::conn::rustls::RustlsConfig>,
+}
+
+impl SalvoServiceWithOptions {
+    /// Set the time-to-live of packets sent by the TCP listener.
+    pub fn with_ttl(mut self, ttl: u32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Serve HTTPS with this TLS configuration instead of plain HTTP.
+    #[cfg(feature = "rustls")]
+    pub fn with_rustls(mut self, config: salvo::conn::rustls::RustlsConfig) -> Self {
+        self.rustls = Some(config);
+        self
+    }
+}
+
+#[shuttle_runtime::async_trait]
+impl shuttle_runtime::Service for SalvoServiceWithOptions {
+    async fn bind(mut self, addr: SocketAddr) -> Result<(), Error> {
+        let mut listener = salvo::conn::TcpListener::new(addr);
+        if let Some(ttl) = self.ttl {
+            listener = listener.ttl(ttl);
+        }
+
+        #[cfg(feature = "rustls")]
+        {
+            if let Some(config) = self.rustls.take() {
+                let acceptor = listener.rustls(config).bind().await;
+                salvo::Server::new(acceptor).serve(self.router).await;
+
+                return Ok(());
+            }
+        }
+
+        let acceptor = listener.bind().await;
+        salvo::Server::new(acceptor).serve(self.router).await;
+
+        Ok(())
+    }
+}
+
+impl From<SalvoService> for SalvoServiceWithOptions {
+    fn from(service: SalvoService) -> Self {
+        Self {
+            router: service.0,
+            ttl: None,
+            #[cfg(feature = "rustls")]
+            rustls: None,
+        }
+    }
+}
+
 #[doc = include_str!("../README.md")]
 pub type ShuttleSalvo = Result<SalvoService, Error>;
@@ -0,0 +1,109 @@
+//! Run a Shuttle service locally in integration tests.
+//!
+//! The service is built and started like with `shuttle run`, so resources such as databases
+//! are provisioned in Docker containers by the local provisioner.
+//!
+//! ```rust,ignore
+//! #[tokio::test(flavor = "multi_thread")]
+//! async fn hello_world() {
+//!     let service = shuttle_test::spawn(".").await.unwrap();
+//!
+//!     let body = reqwest::get(service.url()).await.unwrap().text().await.unwrap();
+//!     assert_eq!(body, "Hello, world!");
+//! }
+//! ```
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use cargo_shuttle::{Binary, Command, ProjectArgs, RunArgs, SecretsArgs, Shuttle, ShuttleArgs};
+use tokio::{net::TcpStream, task::JoinHandle, time::sleep};
+
+/// A service started by [`spawn`]. The service is stopped when this is dropped.
+pub struct TestService {
+    addr: SocketAddr,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl TestService {
+    /// Address the service is listening on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL of the service, e.g. `http://127.0.0.1:8123`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for TestService {
+    fn drop(&mut self) {
+        // Dropping the local run kills the service process
+        self.handle.abort();
+    }
+}
+
+/// Build and start the Shuttle service in `project_dir`, and wait until it accepts connections.
+///
+/// Secrets are read from `Secrets.dev.toml` or `Secrets.toml` in the project, see [`spawn_with_secrets`]
+/// to use another file.
+pub async fn spawn(project_dir: impl AsRef<Path>) -> Result<TestService> {
+    spawn_with_secrets(project_dir, None).await
+}
+
+/// Like [`spawn`], but with the secrets in the given file
+pub async fn spawn_with_secrets(
+    project_dir: impl AsRef<Path>,
+    secrets: Option<PathBuf>,
+) -> Result<TestService> {
+    let working_directory = dunce::canonicalize(project_dir.as_ref()).with_context(|| {
+        format!(
+            "project directory {} not found",
+            project_dir.as_ref().display()
+        )
+    })?;
+    let port = portpicker::pick_unused_port().context("no free port for the service")?;
+    let addr = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port);
+
+    let args = ShuttleArgs {
+        api_url: None,
+        profile: None,
+        project_args: ProjectArgs {
+            working_directory: working_directory.clone(),
+            name_or_id: None,
+        },
+        offline: false,
+        debug: false,
+        cmd: Command::Run(RunArgs {
+            port,
+            external: false,
+            release: false,
+            raw: true,
+            watch: false,
+            port_map: Vec::new(),
+            docker: false,
+            seed: Vec::new(),
+            reseed: false,
+            secret_args: SecretsArgs { secrets },
+        }),
+    };
+    let handle = tokio::spawn(Shuttle::new(Binary::Shuttle)?.run(args, false));
+
+    // The first build can take minutes, so this relies on the timeout of the test instead of its own
+    while TcpStream::connect(addr).await.is_err() {
+        if handle.is_finished() {
+            handle.await??;
+            bail!(
+                "service in {} stopped before accepting connections",
+                working_directory.display()
+            );
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    Ok(TestService { addr, handle })
+}